@@ -0,0 +1,16 @@
+//! Embedded web dashboard
+//!
+//! A single self-contained HTML page (assets compiled into the binary) that
+//! renders the same data as the CLI read commands — deployment list,
+//! per-deployment replica/worker health, service-discovery endpoints, and GPU
+//! utilization — by polling the existing REST API live.
+
+use axum::response::Html;
+
+/// The compiled-in dashboard page.
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// Serve the embedded dashboard.
+pub async fn serve_dashboard() -> Html<&'static str> {
+    Html(DASHBOARD_HTML)
+}
@@ -5,6 +5,7 @@
 //! - Worker listing
 //! - System status
 
+pub mod dashboard;
 pub mod rest;
 
 pub use rest::create_router;
@@ -1,17 +1,17 @@
 //! REST API handlers
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
     routing::{delete, get, post},
     Router,
 };
 use dmrlet_core::{
     BackendType, DeploymentSpec, DeploymentStatus, DmrletError, Endpoint, GpuInfo,
-    ResourceRequirements, detect_gpus,
+    PlacementStrategyKind, ResourceRequirements, detect_gpus,
 };
-use dmrlet_scheduler::Scheduler;
+use dmrlet_scheduler::{NodeDescriptor, Scheduler};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::info;
@@ -22,21 +22,93 @@ pub struct AppState {
     pub scheduler: Arc<Scheduler>,
 }
 
+/// Structured error body returned to clients.
+///
+/// Carries both a stable machine-readable `code` and a human-readable
+/// `reason` so callers can branch programmatically without string-matching.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorBody {
+    pub code: String,
+    pub reason: String,
+}
+
+/// API error that renders as a structured JSON body with the right status.
+pub struct ApiError {
+    status: StatusCode,
+    body: ErrorBody,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, code: &str, reason: String) -> Self {
+        Self {
+            status,
+            body: ErrorBody {
+                code: code.to_string(),
+                reason,
+            },
+        }
+    }
+}
+
+impl From<DmrletError> for ApiError {
+    fn from(err: DmrletError) -> Self {
+        // Map each error to an HTTP status while preserving its code/reason so
+        // a downstream cause (e.g. a registry auth failure during a pull) is
+        // propagated end to end rather than flattened.
+        let status = match err {
+            DmrletError::DeploymentNotFound(_)
+            | DmrletError::WorkerNotFound(_)
+            | DmrletError::ModelNotFound(_) => StatusCode::NOT_FOUND,
+            DmrletError::ResourceExhausted(_) => StatusCode::SERVICE_UNAVAILABLE,
+            DmrletError::Config(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError::new(status, err.code(), err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
 /// Create the API router
 pub fn create_router(scheduler: Arc<Scheduler>) -> Router {
     let state = Arc::new(AppState { scheduler });
 
-    Router::new()
+    let router = Router::new()
         .route("/api/v1/deployments", post(create_deployment))
         .route("/api/v1/deployments", get(list_deployments))
         .route("/api/v1/deployments/:id", get(get_deployment))
         .route("/api/v1/deployments/:id", delete(delete_deployment))
         .route("/api/v1/deployments/:id/scale", post(scale_deployment))
         .route("/api/v1/deployments/:id/workers", get(get_workers))
+        .route("/api/v1/workers/:id/heartbeat", post(worker_heartbeat))
+        .route("/api/v1/nodes", post(register_node))
         .route("/api/v1/endpoints", get(get_endpoints))
         .route("/api/v1/gpus", get(get_gpus))
         .route("/api/v1/status", get(get_status))
-        .with_state(state)
+        .route("/api/v1/cache/scrub", post(scrub_cache))
+        .route("/", get(crate::dashboard::serve_dashboard))
+        .route("/dashboard", get(crate::dashboard::serve_dashboard));
+
+    #[cfg(feature = "metrics")]
+    let router = router.route("/metrics", get(serve_metrics));
+
+    router.with_state(state)
+}
+
+/// Serve the process metrics in Prometheus text exposition format.
+#[cfg(feature = "metrics")]
+async fn serve_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    use axum::http::header::CONTENT_TYPE;
+    // Refresh the live state gauges (worker/GPU/port counts) before rendering.
+    state.scheduler.export_metrics().await;
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        dmrlet_core::metrics::render(),
+    )
 }
 
 /// Request to create a deployment
@@ -58,6 +130,12 @@ pub struct CreateDeploymentRequest {
     /// Context size
     #[serde(default = "default_context_size")]
     pub context_size: u32,
+    /// Placement constraints as `key=value` tags a node must carry
+    #[serde(default)]
+    pub placement_tags: Vec<String>,
+    /// GPU placement strategy (`default`, `bin-pack`, `spread`, or `rendezvous`)
+    #[serde(default)]
+    pub strategy: PlacementStrategyKind,
 }
 
 fn default_replicas() -> u32 {
@@ -76,7 +154,9 @@ pub struct DeploymentResponse {
     pub model: String,
     pub replicas: u32,
     pub ready_replicas: u32,
+    pub draining_replicas: u32,
     pub phase: String,
+    pub strategy: String,
 }
 
 impl From<DeploymentStatus> for DeploymentResponse {
@@ -87,7 +167,9 @@ impl From<DeploymentStatus> for DeploymentResponse {
             model: status.spec.model,
             replicas: status.spec.replicas,
             ready_replicas: status.ready_replicas,
+            draining_replicas: status.draining_replicas,
             phase: status.phase.to_string(),
+            strategy: status.strategy.to_string(),
         }
     }
 }
@@ -96,7 +178,7 @@ impl From<DeploymentStatus> for DeploymentResponse {
 async fn create_deployment(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateDeploymentRequest>,
-) -> Result<Json<DeploymentResponse>, (StatusCode, String)> {
+) -> Result<Json<DeploymentResponse>, ApiError> {
     info!(
         name = %req.name,
         model = %req.model,
@@ -110,8 +192,10 @@ async fn create_deployment(
         memory: None,
         gpu_count: req.gpu_count,
         gpu_ids: Vec::new(),
+        placement_tags: req.placement_tags,
     };
     spec.backend.context_size = req.context_size;
+    spec.placement_strategy = req.strategy;
 
     if !req.backend.is_empty() {
         spec.backend.backend_type = match req.backend.to_lowercase().as_str() {
@@ -122,17 +206,9 @@ async fn create_deployment(
         };
     }
 
-    let id = state
-        .scheduler
-        .create_deployment(spec)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let id = state.scheduler.create_deployment(spec).await?;
 
-    let status = state
-        .scheduler
-        .get_deployment_status(id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let status = state.scheduler.get_deployment_status(id).await?;
 
     Ok(Json(DeploymentResponse::from(status)))
 }
@@ -140,7 +216,7 @@ async fn create_deployment(
 /// List all deployments
 async fn list_deployments(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<DeploymentResponse>>, (StatusCode, String)> {
+) -> Result<Json<Vec<DeploymentResponse>>, ApiError> {
     let deployments = state.scheduler.list_deployments().await;
     let responses: Vec<DeploymentResponse> = deployments
         .into_iter()
@@ -153,35 +229,27 @@ async fn list_deployments(
 async fn get_deployment(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<DeploymentResponse>, (StatusCode, String)> {
-    let status = state
-        .scheduler
-        .get_deployment_status(id)
-        .await
-        .map_err(|e| match e {
-            DmrletError::DeploymentNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-        })?;
-
+) -> Result<Json<DeploymentResponse>, ApiError> {
+    let status = state.scheduler.get_deployment_status(id).await?;
     Ok(Json(DeploymentResponse::from(status)))
 }
 
+/// Query parameter shared by the delete and scale routes to bypass the
+/// drain grace period and tear down removed workers immediately.
+#[derive(Debug, Deserialize)]
+pub struct ForceQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// Delete a deployment
 async fn delete_deployment(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    info!(deployment_id = %id, "Deleting deployment");
-
-    state
-        .scheduler
-        .delete_deployment(id)
-        .await
-        .map_err(|e| match e {
-            DmrletError::DeploymentNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-        })?;
-
+    Query(query): Query<ForceQuery>,
+) -> Result<StatusCode, ApiError> {
+    info!(deployment_id = %id, force = query.force, "Deleting deployment");
+    state.scheduler.delete_deployment(id, query.force).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -195,28 +263,22 @@ pub struct ScaleRequest {
 async fn scale_deployment(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
+    Query(query): Query<ForceQuery>,
     Json(req): Json<ScaleRequest>,
-) -> Result<Json<DeploymentResponse>, (StatusCode, String)> {
+) -> Result<Json<DeploymentResponse>, ApiError> {
     info!(
         deployment_id = %id,
         replicas = req.replicas,
+        force = query.force,
         "Scaling deployment"
     );
 
     state
         .scheduler
-        .scale_deployment(id, req.replicas)
-        .await
-        .map_err(|e| match e {
-            DmrletError::DeploymentNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-        })?;
-
-    let status = state
-        .scheduler
-        .get_deployment_status(id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .scale_deployment(id, req.replicas, query.force)
+        .await?;
+
+    let status = state.scheduler.get_deployment_status(id).await?;
 
     Ok(Json(DeploymentResponse::from(status)))
 }
@@ -229,13 +291,14 @@ pub struct WorkerResponse {
     pub status: String,
     pub endpoint: String,
     pub gpu_ids: Vec<u32>,
+    pub active_requests: u32,
 }
 
 /// Get workers for a deployment
 async fn get_workers(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Vec<WorkerResponse>>, (StatusCode, String)> {
+) -> Result<Json<Vec<WorkerResponse>>, ApiError> {
     let workers = state.scheduler.get_workers(id).await;
 
     let responses: Vec<WorkerResponse> = workers
@@ -246,22 +309,91 @@ async fn get_workers(
             status: w.status.to_string(),
             endpoint: w.endpoint.url(),
             gpu_ids: w.gpu_ids,
+            active_requests: w.active_requests,
         })
         .collect();
 
     Ok(Json(responses))
 }
 
+/// Request body for a worker heartbeat.
+///
+/// `active_requests` is optional so older workers that don't yet report it
+/// keep working; when present it lets a draining worker be torn down as soon
+/// as it empties out instead of waiting for the full grace period.
+#[derive(Debug, Default, Deserialize)]
+pub struct HeartbeatRequest {
+    #[serde(default)]
+    pub active_requests: Option<u32>,
+}
+
+/// Record a heartbeat from a worker, keeping it alive in the reconciler's view.
+///
+/// The body is optional and best-effort: older workers that send no body (or
+/// one without `active_requests`) simply don't update the in-flight count.
+async fn worker_heartbeat(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    body: axum::body::Bytes,
+) -> Result<StatusCode, ApiError> {
+    let active_requests = serde_json::from_slice::<HeartbeatRequest>(&body)
+        .ok()
+        .and_then(|req| req.active_requests);
+    state.scheduler.heartbeat(id, active_requests).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Request to register (or refresh) a cluster node.
+///
+/// The node reports its own `gpu_info` since the control plane has no way to
+/// detect hardware on a remote host.
+#[derive(Debug, Deserialize)]
+pub struct RegisterNodeRequest {
+    /// Stable node identifier.
+    pub id: String,
+    /// Reachable host address (IP or hostname, no port) for workers placed on this node.
+    pub address: String,
+    /// Failure domain (rack, availability zone, ...).
+    #[serde(default)]
+    pub zone: String,
+    /// Relative capacity weight; higher takes proportionally more replicas.
+    #[serde(default = "default_node_capacity")]
+    pub capacity: u32,
+    /// Free-form `key=value` tags used for constraint matching.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// GPU inventory detected locally on the node.
+    pub gpu_info: GpuInfo,
+}
+
+fn default_node_capacity() -> u32 {
+    1
+}
+
+/// Register (or refresh) a cluster node and its GPU inventory.
+async fn register_node(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<RegisterNodeRequest>,
+) -> Result<StatusCode, ApiError> {
+    info!(node = %req.id, address = %req.address, "Registering node");
+
+    let mut descriptor = NodeDescriptor::new(req.id, req.zone, req.capacity).with_address(req.address);
+    descriptor.tags = req.tags;
+
+    state.scheduler.register_node(descriptor, req.gpu_info).await;
+    Ok(StatusCode::CREATED)
+}
+
 /// Get all endpoints for direct access
 async fn get_endpoints(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<Vec<Endpoint>>, (StatusCode, String)> {
+) -> Result<Json<Vec<Endpoint>>, ApiError> {
     let endpoints = state.scheduler.get_all_endpoints().await;
     Ok(Json(endpoints))
 }
 
 /// Get GPU information
-async fn get_gpus() -> Result<Json<GpuInfo>, (StatusCode, String)> {
+async fn get_gpus() -> Result<Json<GpuInfo>, ApiError> {
     let gpu_info = detect_gpus();
     Ok(Json(gpu_info))
 }
@@ -273,12 +405,14 @@ pub struct StatusResponse {
     pub deployments: usize,
     pub workers: usize,
     pub gpus: GpuInfo,
+    pub cache: dmrlet_scheduler::CacheStats,
+    pub evictions: Vec<dmrlet_scheduler::EvictionEvent>,
 }
 
 /// Get system status
 async fn get_status(
     State(state): State<Arc<AppState>>,
-) -> Result<Json<StatusResponse>, (StatusCode, String)> {
+) -> Result<Json<StatusResponse>, ApiError> {
     let deployments = state.scheduler.list_deployments().await;
     let worker_count: usize = deployments.iter().map(|d| d.workers.len()).sum();
 
@@ -287,16 +421,27 @@ async fn get_status(
         deployments: deployments.len(),
         workers: worker_count,
         gpus: detect_gpus(),
+        cache: state.scheduler.cache_stats().await,
+        evictions: state.scheduler.cache_evictions().await,
     }))
 }
 
+/// Scrub the model cache for missing, corrupt, or orphaned blobs and repair
+/// what it finds.
+async fn scrub_cache(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<dmrlet_scheduler::ScrubReport>, ApiError> {
+    let report = state.scheduler.cache_scrub().await?;
+    Ok(Json(report))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
     async fn test_create_router() {
-        let scheduler = Arc::new(Scheduler::new(30000, 100));
+        let scheduler = Arc::new(Scheduler::ephemeral(30000, 100).await);
         let _router = create_router(scheduler);
     }
 }
@@ -72,6 +72,23 @@ pub struct GpuInfo {
     pub available_count: u32,
 }
 
+/// Structured error body returned by the daemon
+#[derive(Debug, Deserialize)]
+pub struct ApiErrorBody {
+    pub code: String,
+    pub reason: String,
+}
+
+/// Read a failure response, surfacing the daemon's structured `code`/`reason`
+/// when present and falling back to the HTTP status otherwise.
+pub async fn read_error(response: reqwest::Response) -> String {
+    let status = response.status();
+    match response.json::<ApiErrorBody>().await {
+        Ok(body) => format!("[{}] {}", body.code, body.reason),
+        Err(_) => format!("HTTP {}", status),
+    }
+}
+
 /// Status response
 #[derive(Debug, Deserialize)]
 pub struct StatusResponse {
@@ -134,7 +151,7 @@ pub async fn deploy(
         println!("  Replicas: {}/{}", deployment.ready_replicas, deployment.replicas);
         println!("  Phase: {}", deployment.phase);
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to create deployment: {}", error);
     }
 
@@ -142,7 +159,7 @@ pub async fn deploy(
 }
 
 /// Scale a deployment
-pub async fn scale(client: &ApiClient, deployment: String, replicas: u32) -> Result<()> {
+pub async fn scale(client: &ApiClient, deployment: String, replicas: u32, force: bool) -> Result<()> {
     // Try to parse as UUID first, otherwise search by name
     let id = parse_deployment_id(client, &deployment).await?;
 
@@ -153,7 +170,10 @@ pub async fn scale(client: &ApiClient, deployment: String, replicas: u32) -> Res
 
     let response = client
         .client
-        .post(client.url(&format!("/api/v1/deployments/{}/scale", id)))
+        .post(client.url(&format!(
+            "/api/v1/deployments/{}/scale?force={}",
+            id, force
+        )))
         .json(&ScaleRequest { replicas })
         .send()
         .await?;
@@ -162,7 +182,7 @@ pub async fn scale(client: &ApiClient, deployment: String, replicas: u32) -> Res
         let deployment: DeploymentResponse = response.json().await?;
         println!("Deployment '{}' scaled to {} replicas", deployment.name, replicas);
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to scale deployment: {}", error);
     }
 
@@ -170,19 +190,19 @@ pub async fn scale(client: &ApiClient, deployment: String, replicas: u32) -> Res
 }
 
 /// Delete a deployment
-pub async fn delete(client: &ApiClient, deployment: String) -> Result<()> {
+pub async fn delete(client: &ApiClient, deployment: String, force: bool) -> Result<()> {
     let id = parse_deployment_id(client, &deployment).await?;
 
     let response = client
         .client
-        .delete(client.url(&format!("/api/v1/deployments/{}", id)))
+        .delete(client.url(&format!("/api/v1/deployments/{}?force={}", id, force)))
         .send()
         .await?;
 
     if response.status().is_success() {
         println!("Deployment '{}' deleted", deployment);
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to delete deployment: {}", error);
     }
 
@@ -224,7 +244,7 @@ pub async fn status(client: &ApiClient, deployment: Option<String>) -> Result<()
                     }
                 }
             } else {
-                let error = response.text().await?;
+                let error = read_error(response).await;
                 eprintln!("Deployment not found: {}", error);
             }
         }
@@ -264,7 +284,7 @@ pub async fn ps(client: &ApiClient) -> Result<()> {
             }
         }
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to list deployments: {}", error);
     }
 
@@ -292,7 +312,7 @@ pub async fn endpoints(client: &ApiClient) -> Result<()> {
             }
         }
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to get endpoints: {}", error);
     }
 
@@ -332,7 +352,7 @@ pub async fn gpus(client: &ApiClient) -> Result<()> {
             }
         }
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to get GPU info: {}", error);
     }
 
@@ -340,7 +360,15 @@ pub async fn gpus(client: &ApiClient) -> Result<()> {
 }
 
 /// Show system status
-pub async fn top(client: &ApiClient) -> Result<()> {
+///
+/// `format` is `"text"` for the human-readable summary, or `"prometheus"` to
+/// scrape and print the daemon's `/metrics` endpoint verbatim so it can be
+/// piped straight into a file or a `curl`-free dashboard wire-up.
+pub async fn top(client: &ApiClient, format: &str) -> Result<()> {
+    if format == "prometheus" {
+        return top_prometheus(client).await;
+    }
+
     let response = client
         .client
         .get(client.url("/api/v1/status"))
@@ -359,13 +387,86 @@ pub async fn top(client: &ApiClient) -> Result<()> {
             status.gpus.total_count, status.gpus.available_count
         );
     } else {
-        let error = response.text().await?;
+        let error = read_error(response).await;
         eprintln!("Failed to get status: {}", error);
     }
 
     Ok(())
 }
 
+/// Scrape the daemon's `/metrics` endpoint and print it unmodified.
+async fn top_prometheus(client: &ApiClient) -> Result<()> {
+    let response = client.client.get(client.url("/metrics")).send().await?;
+
+    if response.status().is_success() {
+        print!("{}", response.text().await?);
+    } else {
+        let error = read_error(response).await;
+        eprintln!("Failed to scrape metrics: {}", error);
+    }
+
+    Ok(())
+}
+
+/// Cache scrub report from API
+#[derive(Debug, Deserialize)]
+pub struct ScrubResponse {
+    pub repaired: Vec<String>,
+    pub bytes_reclaimed: u64,
+    pub orphans_removed: Vec<String>,
+    pub orphan_bytes_reclaimed: u64,
+}
+
+/// Scrub the model cache for missing, corrupt, or orphaned blobs
+pub async fn scrub(client: &ApiClient) -> Result<()> {
+    let response = client
+        .client
+        .post(client.url("/api/v1/cache/scrub"))
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        let report: ScrubResponse = response.json().await?;
+
+        println!(
+            "Repaired {} model(s), reclaiming {} bytes",
+            report.repaired.len(),
+            report.bytes_reclaimed
+        );
+        println!(
+            "Removed {} orphaned file(s), reclaiming {} bytes",
+            report.orphans_removed.len(),
+            report.orphan_bytes_reclaimed
+        );
+    } else {
+        let error = read_error(response).await;
+        eprintln!("Failed to scrub cache: {}", error);
+    }
+
+    Ok(())
+}
+
+/// Open the web dashboard in the default browser
+pub async fn dashboard(client: &ApiClient) -> Result<()> {
+    let url = client.url("/dashboard");
+    println!("Opening dashboard at {}", url);
+
+    // Best-effort launch of the platform browser opener.
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+
+    if let Err(e) = std::process::Command::new(opener).arg(&url).spawn() {
+        eprintln!("Could not launch browser ({}); open {} manually", e, url);
+    }
+
+    Ok(())
+}
+
 /// Helper to parse deployment ID (UUID or name)
 async fn parse_deployment_id(client: &ApiClient, deployment: &str) -> Result<Uuid> {
     // Try parsing as UUID first
@@ -56,12 +56,20 @@ enum Commands {
 
         /// Number of replicas
         replicas: u32,
+
+        /// Skip the drain grace period and remove workers immediately
+        #[arg(long)]
+        force: bool,
     },
 
     /// Delete a deployment
     Delete {
         /// Deployment name or ID
         deployment: String,
+
+        /// Skip the drain grace period and remove workers immediately
+        #[arg(long)]
+        force: bool,
     },
 
     /// Get deployment status
@@ -80,7 +88,17 @@ enum Commands {
     Gpus,
 
     /// Show system status
-    Top,
+    Top {
+        /// Output format: `text` (default) or `prometheus` (scrapes /metrics)
+        #[arg(long, default_value = "text")]
+        format: String,
+    },
+
+    /// Open the web dashboard in a browser
+    Dashboard,
+
+    /// Scrub the model cache for missing, corrupt, or orphaned blobs
+    Scrub,
 }
 
 #[tokio::main]
@@ -115,11 +133,12 @@ async fn main() -> anyhow::Result<()> {
         Commands::Scale {
             deployment,
             replicas,
+            force,
         } => {
-            commands::scale(&client, deployment, replicas).await?;
+            commands::scale(&client, deployment, replicas, force).await?;
         }
-        Commands::Delete { deployment } => {
-            commands::delete(&client, deployment).await?;
+        Commands::Delete { deployment, force } => {
+            commands::delete(&client, deployment, force).await?;
         }
         Commands::Status { deployment } => {
             commands::status(&client, deployment).await?;
@@ -133,8 +152,14 @@ async fn main() -> anyhow::Result<()> {
         Commands::Gpus => {
             commands::gpus(&client).await?;
         }
-        Commands::Top => {
-            commands::top(&client).await?;
+        Commands::Top { format } => {
+            commands::top(&client, &format).await?;
+        }
+        Commands::Dashboard => {
+            commands::dashboard(&client).await?;
+        }
+        Commands::Scrub => {
+            commands::scrub(&client).await?;
         }
     }
 
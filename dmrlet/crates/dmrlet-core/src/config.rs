@@ -16,6 +16,9 @@ pub struct DaemonConfig {
     pub storage: StorageConfig,
     /// Logging configuration
     pub logging: LoggingConfig,
+    /// Cluster membership configuration
+    #[serde(default)]
+    pub cluster: ClusterConfig,
 }
 
 impl Default for DaemonConfig {
@@ -26,6 +29,57 @@ impl Default for DaemonConfig {
             network: NetworkConfig::default(),
             storage: StorageConfig::default(),
             logging: LoggingConfig::default(),
+            cluster: ClusterConfig::default(),
+        }
+    }
+}
+
+/// Cluster membership configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    /// Whether this daemon joins a multi-node cluster.
+    pub enabled: bool,
+    /// Seed peers to contact on startup (host:port).
+    pub peers: Vec<String>,
+    /// SWIM protocol period in milliseconds.
+    pub probe_interval_ms: u64,
+    /// Per-probe ack timeout in milliseconds.
+    pub probe_timeout_ms: u64,
+    /// Number of members used for indirect ping-requests.
+    pub indirect_probes: usize,
+    /// Grace period before a suspected member is declared dead (ms).
+    pub suspect_timeout_ms: u64,
+    /// Failure domain this node belongs to (for zone-aware placement).
+    #[serde(default = "default_zone")]
+    pub zone: String,
+    /// Relative capacity weight used when spreading replicas.
+    #[serde(default = "default_capacity")]
+    pub capacity: u32,
+    /// Free-form `key=value` tags advertised by this node.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_zone() -> String {
+    "default".to_string()
+}
+
+fn default_capacity() -> u32 {
+    1
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            peers: Vec::new(),
+            probe_interval_ms: 1000,
+            probe_timeout_ms: 500,
+            indirect_probes: 3,
+            suspect_timeout_ms: 5000,
+            zone: default_zone(),
+            capacity: default_capacity(),
+            tags: Vec::new(),
         }
     }
 }
@@ -121,6 +175,13 @@ pub struct NetworkConfig {
     pub health_check_interval_secs: u64,
     /// Health check timeout in seconds
     pub health_check_timeout_secs: u64,
+    /// Phi-accrual suspicion threshold for adaptive failure detection
+    #[serde(default = "default_phi_threshold")]
+    pub phi_threshold: f64,
+}
+
+fn default_phi_threshold() -> f64 {
+    8.0
 }
 
 impl Default for NetworkConfig {
@@ -131,6 +192,7 @@ impl Default for NetworkConfig {
             lb_strategy: LoadBalanceStrategy::RoundRobin,
             health_check_interval_secs: 10,
             health_check_timeout_secs: 5,
+            phi_threshold: default_phi_threshold(),
         }
     }
 }
@@ -143,6 +205,8 @@ pub enum LoadBalanceStrategy {
     RoundRobin,
     /// Least connections load balancing
     LeastConnections,
+    /// Power-of-two-choices: sample two endpoints, pick the less loaded
+    PowerOfTwoChoices,
     /// Random load balancing
     Random,
 }
@@ -156,6 +220,15 @@ pub struct StorageConfig {
     pub max_cache_size: u64,
     /// Enable LRU eviction
     pub lru_eviction: bool,
+    /// Evict a cached model once it has been idle (unused) for this many
+    /// seconds, regardless of how much headroom remains under
+    /// `max_cache_size`. `None` disables idle-based expiry.
+    #[serde(default)]
+    pub max_idle_secs: Option<u64>,
+    /// Evict a cached model once this many seconds have passed since it was
+    /// downloaded, regardless of use. `None` disables TTL-based expiry.
+    #[serde(default)]
+    pub max_ttl_secs: Option<u64>,
 }
 
 impl Default for StorageConfig {
@@ -164,6 +237,8 @@ impl Default for StorageConfig {
             models_path: PathBuf::from("/var/lib/dmrlet/models"),
             max_cache_size: 100 * 1024 * 1024 * 1024, // 100 GB
             lru_eviction: true,
+            max_idle_secs: None,
+            max_ttl_secs: None,
         }
     }
 }
@@ -215,6 +290,8 @@ pub struct DeploymentSettings {
 pub struct ResourceSettings {
     pub memory: Option<String>,
     pub gpu_count: Option<u32>,
+    /// Placement constraints as `key=value` tags a node must carry
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +62,31 @@ pub enum DmrletError {
     Internal(String),
 }
 
+impl DmrletError {
+    /// Stable machine-readable code for this error.
+    ///
+    /// Clients and the CLI branch on these codes rather than string-matching
+    /// the human-readable message, so they must not change once published.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DmrletError::Config(_) => "config",
+            DmrletError::Runtime(_) => "runtime",
+            DmrletError::Scheduler(_) => "scheduler",
+            DmrletError::Network(_) => "network",
+            DmrletError::Storage(_) => "storage",
+            DmrletError::Api(_) => "api",
+            DmrletError::Gpu(_) => "gpu",
+            DmrletError::DeploymentNotFound(_) => "deployment_not_found",
+            DmrletError::WorkerNotFound(_) => "worker_not_found",
+            DmrletError::ModelNotFound(_) => "model_not_found",
+            DmrletError::ResourceExhausted(_) => "resource_exhausted",
+            DmrletError::Io(_) => "io",
+            DmrletError::Serialization(_) => "serialization",
+            DmrletError::Internal(_) => "internal",
+        }
+    }
+}
+
 /// Result type for dmrlet operations
 pub type DmrletResult<T> = Result<T, DmrletError>;
 
@@ -87,6 +112,19 @@ mod tests {
         assert_eq!(err.to_string(), "Configuration error: invalid config");
     }
 
+    #[test]
+    fn test_error_code_stable() {
+        assert_eq!(
+            DmrletError::ModelNotFound("x".to_string()).code(),
+            "model_not_found"
+        );
+        assert_eq!(
+            DmrletError::ResourceExhausted("x".to_string()).code(),
+            "resource_exhausted"
+        );
+        assert_eq!(DmrletError::Gpu("x".to_string()).code(), "gpu");
+    }
+
     #[test]
     fn test_error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
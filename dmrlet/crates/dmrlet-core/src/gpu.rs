@@ -2,6 +2,36 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Minimum free memory (in bytes) for a device to be considered available.
+///
+/// A GPU with less free memory than this floor is reported as unavailable so
+/// the scheduler does not place a worker on a nearly-full card.
+pub const AVAILABILITY_MEMORY_FLOOR: u64 = 512 * 1024 * 1024;
+
+/// A compute process running on a GPU.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuProcess {
+    /// Operating-system process id.
+    pub pid: u32,
+    /// GPU memory used by the process, in bytes.
+    pub used_memory: u64,
+    /// Streaming-multiprocessor utilization for the process (0-100), if known.
+    pub sm_utilization: Option<u32>,
+}
+
+/// PCI identity of a GPU, used to distinguish physically identical cards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PciInfo {
+    /// PCI domain.
+    pub domain: u32,
+    /// PCI bus.
+    pub bus: u32,
+    /// PCI device (slot).
+    pub device: u32,
+    /// Fully-qualified bus id, e.g. `00000000:01:00.0`.
+    pub bus_id: String,
+}
+
 /// Represents a GPU device
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpuDevice {
@@ -19,6 +49,21 @@ pub struct GpuDevice {
     pub available: bool,
     /// Current utilization percentage (0-100)
     pub utilization: Option<u32>,
+    /// PCI identity, when the driver exposes it.
+    #[serde(default)]
+    pub pci: Option<PciInfo>,
+    /// Core temperature in degrees Celsius.
+    #[serde(default)]
+    pub temperature_c: Option<u32>,
+    /// Current board power draw in milliwatts.
+    #[serde(default)]
+    pub power_usage_mw: Option<u32>,
+    /// Enforced power limit in milliwatts.
+    #[serde(default)]
+    pub power_limit_mw: Option<u32>,
+    /// Compute processes currently running on the device.
+    #[serde(default)]
+    pub processes: Vec<GpuProcess>,
 }
 
 /// GPU vendor types
@@ -65,6 +110,24 @@ impl GpuInfo {
         }
     }
 
+    /// Append another backend's devices, keeping counts consistent.
+    ///
+    /// NVIDIA device indices are preserved so NVML refreshes still line up;
+    /// devices from other backends are re-indexed to follow on, avoiding
+    /// collisions between vendors that both number from zero.
+    pub fn merge(&mut self, other: GpuInfo) {
+        let mut next_index = self.devices.len() as u32;
+        for mut device in other.devices {
+            if device.vendor != GpuVendor::Nvidia {
+                device.index = next_index;
+            }
+            next_index = next_index.max(device.index + 1);
+            self.devices.push(device);
+        }
+        self.total_count = self.devices.len() as u32;
+        self.available_count = self.devices.iter().filter(|d| d.available).count() as u32;
+    }
+
     /// Get available device indices
     pub fn available_indices(&self) -> Vec<u32> {
         self.devices
@@ -87,26 +150,42 @@ pub fn detect_gpus() -> GpuInfo {
         detect_apple_gpus()
     }
 
+    // On Linux/Windows, merge every vendor backend we can reach. Each backend
+    // is independently fallible, so a missing vendor library (e.g. no ROCm
+    // installed) never zeroes out the others.
     #[cfg(not(target_os = "macos"))]
     {
-        detect_nvidia_gpus().unwrap_or_else(|_| GpuInfo::empty())
+        let mut merged = GpuInfo::empty();
+        merged.merge(detect_nvidia_gpus().unwrap_or_else(|_| GpuInfo::empty()));
+        merged.merge(detect_amd_gpus());
+        merged.merge(detect_intel_gpus());
+        merged
     }
 }
 
 /// Detect Apple Silicon GPUs (macOS only)
 #[cfg(target_os = "macos")]
 fn detect_apple_gpus() -> GpuInfo {
-    // On macOS, we assume Apple Silicon with unified memory
-    // The actual GPU capabilities would be detected via Metal APIs
-    // For now, we return a single Apple GPU
+    // Apple Silicon shares one unified memory pool between CPU and GPU, so the
+    // GPU-accessible pool is approximated by total system memory (hw.memsize).
+    let memory_total = sysctl_u64("hw.memsize").unwrap_or(0);
+    // The chip marketing name (e.g. "Apple M2 Max") comes from the CPU brand.
+    let name = sysctl_string("machdep.cpu.brand_string")
+        .unwrap_or_else(|| "Apple Silicon GPU".to_string());
+
     let device = GpuDevice {
         index: 0,
-        name: "Apple Silicon GPU".to_string(),
-        memory_total: 0, // Unified memory, would need sysctl to get
-        memory_free: 0,
+        name,
+        memory_total,
+        memory_free: memory_total,
         vendor: GpuVendor::Apple,
         available: true,
         utilization: None,
+        pci: None,
+        temperature_c: None,
+        power_usage_mw: None,
+        power_limit_mw: None,
+        processes: Vec::new(),
     };
 
     GpuInfo {
@@ -116,12 +195,257 @@ fn detect_apple_gpus() -> GpuInfo {
     }
 }
 
+/// Read an integer `sysctl` value by name.
+#[cfg(target_os = "macos")]
+fn sysctl_u64(name: &str) -> Option<u64> {
+    sysctl_string(name).and_then(|s| s.parse().ok())
+}
+
+/// Read a `sysctl` value as a trimmed string.
+#[cfg(target_os = "macos")]
+fn sysctl_string(name: &str) -> Option<String> {
+    let output = std::process::Command::new("sysctl")
+        .arg("-n")
+        .arg(name)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Process-wide NVML handle, initialized lazily on first GPU detection.
+#[cfg(not(target_os = "macos"))]
+static NVML: std::sync::OnceLock<Option<nvml_wrapper::Nvml>> = std::sync::OnceLock::new();
+
+/// Borrow the shared NVML handle, attempting initialization once.
+///
+/// Returns `None` when the driver or NVML library is unavailable, so callers
+/// can fall back to an empty inventory without surfacing an error.
+#[cfg(not(target_os = "macos"))]
+fn nvml() -> Option<&'static nvml_wrapper::Nvml> {
+    NVML.get_or_init(|| nvml_wrapper::Nvml::init().ok())
+        .as_ref()
+}
+
 /// Detect NVIDIA GPUs using NVML
 #[cfg(not(target_os = "macos"))]
 fn detect_nvidia_gpus() -> Result<GpuInfo, crate::DmrletError> {
-    // NVML detection would go here
-    // For now, return empty as NVML might not be available
-    Ok(GpuInfo::empty())
+    // NVML is optional: a host without an NVIDIA driver simply reports no GPUs.
+    let Some(nvml) = nvml() else {
+        return Ok(GpuInfo::empty());
+    };
+
+    let count = nvml
+        .device_count()
+        .map_err(|e| crate::DmrletError::Gpu(format!("NVML device_count failed: {}", e)))?;
+
+    let mut devices = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let device = nvml
+            .device_by_index(i)
+            .map_err(|e| crate::DmrletError::Gpu(format!("NVML device_by_index({}): {}", i, e)))?;
+
+        let name = device.name().unwrap_or_else(|_| format!("NVIDIA GPU {}", i));
+        let memory = device
+            .memory_info()
+            .map_err(|e| crate::DmrletError::Gpu(format!("NVML memory_info: {}", e)))?;
+        let pci = device.pci_info().ok().map(|p| PciInfo {
+            domain: p.domain,
+            bus: p.bus,
+            device: p.device,
+            bus_id: p.bus_id,
+        });
+        // The enforced power limit is effectively static, so read it once here.
+        let power_limit_mw = device.enforced_power_limit().ok();
+
+        let mut gpu = GpuDevice {
+            index: i,
+            name,
+            memory_total: memory.total,
+            memory_free: memory.free,
+            vendor: GpuVendor::Nvidia,
+            available: memory.free > AVAILABILITY_MEMORY_FLOOR,
+            utilization: None,
+            pci,
+            temperature_c: None,
+            power_usage_mw: None,
+            power_limit_mw,
+            processes: Vec::new(),
+        };
+        refresh_device(&device, &mut gpu);
+        devices.push(gpu);
+    }
+
+    let available_count = devices.iter().filter(|d| d.available).count() as u32;
+    Ok(GpuInfo {
+        total_count: devices.len() as u32,
+        available_count,
+        devices,
+    })
+}
+
+/// PCI vendor id reported by AMD GPUs in sysfs.
+#[cfg(not(target_os = "macos"))]
+const PCI_VENDOR_AMD: &str = "0x1002";
+
+/// PCI vendor id reported by Intel GPUs in sysfs.
+#[cfg(not(target_os = "macos"))]
+const PCI_VENDOR_INTEL: &str = "0x8086";
+
+/// Detect AMD GPUs via the amdgpu sysfs interface.
+///
+/// Reads `/sys/class/drm/card*/device` entries whose PCI vendor is AMD and
+/// reports their total VRAM from `mem_info_vram_total`. Falls back to an empty
+/// inventory when the nodes are absent (no ROCm/amdgpu driver).
+#[cfg(not(target_os = "macos"))]
+fn detect_amd_gpus() -> GpuInfo {
+    detect_sysfs_gpus(PCI_VENDOR_AMD, GpuVendor::Amd, "AMD GPU")
+}
+
+/// Detect Intel GPUs via the i915/xe sysfs interface.
+///
+/// Mirrors the AMD path: discrete Intel cards expose `mem_info_vram_total`,
+/// while integrated parts report no dedicated VRAM. Absent nodes yield an
+/// empty inventory. A full implementation would consult Level-Zero for richer
+/// telemetry.
+#[cfg(not(target_os = "macos"))]
+fn detect_intel_gpus() -> GpuInfo {
+    detect_sysfs_gpus(PCI_VENDOR_INTEL, GpuVendor::Intel, "Intel GPU")
+}
+
+/// Shared sysfs scan used by the AMD and Intel backends.
+#[cfg(not(target_os = "macos"))]
+fn detect_sysfs_gpus(vendor_id: &str, vendor: GpuVendor, default_name: &str) -> GpuInfo {
+    let mut devices = Vec::new();
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return GpuInfo::empty();
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        // Match the primary `cardN` nodes, not their `cardN-<connector>` kin.
+        if !name.starts_with("card") || name.contains('-') {
+            continue;
+        }
+
+        let device_dir = entry.path().join("device");
+        let reported_vendor = std::fs::read_to_string(device_dir.join("vendor"))
+            .map(|s| s.trim().to_lowercase())
+            .unwrap_or_default();
+        if reported_vendor != vendor_id {
+            continue;
+        }
+
+        let memory_total = std::fs::read_to_string(device_dir.join("mem_info_vram_total"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        let memory_free = std::fs::read_to_string(device_dir.join("mem_info_vram_used"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|used| memory_total.saturating_sub(used))
+            .unwrap_or(memory_total);
+
+        devices.push(GpuDevice {
+            index: devices.len() as u32,
+            name: default_name.to_string(),
+            memory_total,
+            memory_free,
+            vendor,
+            available: memory_free > AVAILABILITY_MEMORY_FLOOR,
+            utilization: None,
+            pci: None,
+            temperature_c: None,
+            power_usage_mw: None,
+            power_limit_mw: None,
+            processes: Vec::new(),
+        });
+    }
+
+    let available_count = devices.iter().filter(|d| d.available).count() as u32;
+    GpuInfo {
+        total_count: devices.len() as u32,
+        available_count,
+        devices,
+    }
+}
+
+/// Re-read the dynamic fields of a single device from NVML in place.
+///
+/// Updates free memory, utilization, temperature, power draw, and the running
+/// compute-process list; the device's identity fields (name, PCI, limits) are
+/// left untouched.
+#[cfg(not(target_os = "macos"))]
+fn refresh_device(device: &nvml_wrapper::Device<'_>, gpu: &mut GpuDevice) {
+    use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+
+    if let Ok(memory) = device.memory_info() {
+        gpu.memory_free = memory.free;
+        gpu.available = memory.free > AVAILABILITY_MEMORY_FLOOR;
+    }
+    gpu.utilization = device.utilization_rates().ok().map(|u| u.gpu);
+    gpu.temperature_c = device.temperature(TemperatureSensor::Gpu).ok();
+    gpu.power_usage_mw = device.power_usage().ok();
+
+    // Correlate per-process memory with per-process SM utilization.
+    if let Ok(procs) = device.running_compute_processes() {
+        let util = device
+            .process_utilization_stats(None)
+            .unwrap_or_default();
+        gpu.processes = procs
+            .into_iter()
+            .map(|p| GpuProcess {
+                pid: p.pid,
+                used_memory: match p.used_gpu_memory {
+                    nvml_wrapper::enums::device::UsedGpuMemory::Used(bytes) => bytes,
+                    nvml_wrapper::enums::device::UsedGpuMemory::Unavailable => 0,
+                },
+                sm_utilization: util
+                    .iter()
+                    .find(|s| s.pid == p.pid)
+                    .map(|s| s.sm_util),
+            })
+            .collect();
+    }
+}
+
+impl GpuInfo {
+    /// Re-read the dynamic fields of every NVIDIA device in place.
+    ///
+    /// This does not re-enumerate devices: it refreshes free memory,
+    /// utilization, temperature, power, and running processes on the existing
+    /// NVIDIA `GpuDevice` entries so a monitor loop can poll current state
+    /// cheaply. Devices of other vendors keep the values read at detection. A
+    /// no-op where NVML is unavailable.
+    #[cfg(not(target_os = "macos"))]
+    pub fn refresh(&mut self) {
+        let Some(nvml) = nvml() else {
+            return;
+        };
+        for gpu in &mut self.devices {
+            if gpu.vendor != GpuVendor::Nvidia {
+                continue;
+            }
+            if let Ok(device) = nvml.device_by_index(gpu.index) {
+                refresh_device(&device, gpu);
+            }
+        }
+        self.available_count = self.devices.iter().filter(|d| d.available).count() as u32;
+    }
+
+    /// Re-read the dynamic fields of every device in place.
+    ///
+    /// On macOS there is no NVML backend, so this is a no-op.
+    #[cfg(target_os = "macos")]
+    pub fn refresh(&mut self) {}
 }
 
 #[cfg(test)]
@@ -147,6 +471,11 @@ mod tests {
                     vendor: GpuVendor::Nvidia,
                     available: true,
                     utilization: Some(50),
+                    pci: None,
+                    temperature_c: None,
+                    power_usage_mw: None,
+                    power_limit_mw: None,
+                    processes: Vec::new(),
                 },
                 GpuDevice {
                     index: 1,
@@ -156,6 +485,11 @@ mod tests {
                     vendor: GpuVendor::Nvidia,
                     available: false,
                     utilization: Some(100),
+                    pci: None,
+                    temperature_c: None,
+                    power_usage_mw: None,
+                    power_limit_mw: None,
+                    processes: Vec::new(),
                 },
             ],
             total_count: 2,
@@ -6,13 +6,18 @@
 //! - Configuration types
 //! - Error handling
 //! - GPU detection and allocation
+//! - DNS-over-HTTPS name resolution
 
 pub mod config;
 pub mod error;
 pub mod gpu;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod model;
+pub mod resolver;
 
 pub use config::*;
 pub use error::*;
 pub use gpu::*;
 pub use model::*;
+pub use resolver::Resolver;
@@ -0,0 +1,187 @@
+//! Observability metrics subsystem.
+//!
+//! A small process-global registry of counters, gauges, and latency
+//! histograms that the scheduler, GPU allocator, load balancer, and health
+//! checker record into. The registry renders in Prometheus text exposition
+//! format so it can be scraped from the daemon's `/metrics` route.
+//!
+//! The whole subsystem is gated behind the `metrics` cargo feature; minimal
+//! builds compile none of it and pay no runtime cost.
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Fixed latency histogram buckets, in seconds.
+const LATENCY_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// A metric series identified by its name and sorted label set.
+type Series = (String, Vec<(String, String)>);
+
+/// A cumulative histogram with fixed buckets plus running sum and count.
+#[derive(Default)]
+struct Histogram {
+    /// Count of observations falling at or below each bucket boundary.
+    counts: Vec<u64>,
+    /// Sum of all observed values.
+    sum: f64,
+    /// Total number of observations.
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if self.counts.is_empty() {
+            self.counts = vec![0; LATENCY_BUCKETS.len()];
+        }
+        for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+            if value <= *bound {
+                self.counts[i] += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Process-global metrics registry.
+#[derive(Default)]
+pub struct Registry {
+    counters: Mutex<BTreeMap<Series, u64>>,
+    gauges: Mutex<BTreeMap<Series, f64>>,
+    histograms: Mutex<BTreeMap<Series, Histogram>>,
+}
+
+/// Return the shared registry, initializing it on first use.
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::default)
+}
+
+/// Normalize a borrowed label slice into an owned, sorted series key.
+fn series(name: &str, labels: &[(&str, &str)]) -> Series {
+    let mut owned: Vec<(String, String)> = labels
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    owned.sort();
+    (name.to_string(), owned)
+}
+
+/// Add `by` to a counter series.
+pub fn counter_add(name: &str, labels: &[(&str, &str)], by: u64) {
+    let mut counters = registry().counters.lock().unwrap();
+    *counters.entry(series(name, labels)).or_insert(0) += by;
+}
+
+/// Increment a counter series by one.
+pub fn counter_inc(name: &str, labels: &[(&str, &str)]) {
+    counter_add(name, labels, 1);
+}
+
+/// Set a gauge series to an absolute value.
+pub fn gauge_set(name: &str, labels: &[(&str, &str)], value: f64) {
+    let mut gauges = registry().gauges.lock().unwrap();
+    gauges.insert(series(name, labels), value);
+}
+
+/// Record a single observation into a latency histogram series.
+pub fn histogram_observe(name: &str, labels: &[(&str, &str)], value: f64) {
+    let mut histograms = registry().histograms.lock().unwrap();
+    histograms
+        .entry(series(name, labels))
+        .or_default()
+        .observe(value);
+}
+
+/// Render label pairs as a Prometheus label set, optionally with an extra pair.
+fn format_labels(labels: &[(String, String)], extra: Option<(&str, &str)>) -> String {
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, v))
+        .collect();
+    if let Some((k, v)) = extra {
+        parts.push(format!("{}=\"{}\"", k, v));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+/// Render the whole registry in Prometheus text exposition format.
+pub fn render() -> String {
+    let mut out = String::new();
+
+    {
+        let counters = registry().counters.lock().unwrap();
+        for ((name, labels), value) in counters.iter() {
+            out.push_str(&format!("{}{} {}\n", name, format_labels(labels, None), value));
+        }
+    }
+
+    {
+        let gauges = registry().gauges.lock().unwrap();
+        for ((name, labels), value) in gauges.iter() {
+            out.push_str(&format!("{}{} {}\n", name, format_labels(labels, None), value));
+        }
+    }
+
+    {
+        let histograms = registry().histograms.lock().unwrap();
+        for ((name, labels), hist) in histograms.iter() {
+            for (i, bound) in LATENCY_BUCKETS.iter().enumerate() {
+                let count = hist.counts.get(i).copied().unwrap_or(0);
+                out.push_str(&format!(
+                    "{}_bucket{} {}\n",
+                    name,
+                    format_labels(labels, Some("le", &bound.to_string())),
+                    count
+                ));
+            }
+            out.push_str(&format!(
+                "{}_bucket{} {}\n",
+                name,
+                format_labels(labels, Some("le", "+Inf")),
+                hist.count
+            ));
+            out.push_str(&format!(
+                "{}_sum{} {}\n",
+                name,
+                format_labels(labels, None),
+                hist.sum
+            ));
+            out.push_str(&format!(
+                "{}_count{} {}\n",
+                name,
+                format_labels(labels, None),
+                hist.count
+            ));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_and_render() {
+        counter_inc("dmrlet_test_counter", &[("kind", "a")]);
+        counter_inc("dmrlet_test_counter", &[("kind", "a")]);
+        let text = render();
+        assert!(text.contains("dmrlet_test_counter{kind=\"a\"} 2"));
+    }
+
+    #[test]
+    fn test_histogram_observe() {
+        histogram_observe("dmrlet_test_latency", &[], 0.003);
+        let text = render();
+        assert!(text.contains("dmrlet_test_latency_count"));
+        assert!(text.contains("dmrlet_test_latency_bucket"));
+    }
+}
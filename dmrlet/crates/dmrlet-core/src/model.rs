@@ -1,5 +1,6 @@
 //! Model, Worker, and Endpoint type definitions
 
+use crate::resolver::Resolver;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -23,6 +24,9 @@ pub struct DeploymentSpec {
     pub health: HealthConfig,
     /// Auto-scaling configuration
     pub autoscale: Option<AutoscaleConfig>,
+    /// GPU placement strategy for this deployment's workers
+    #[serde(default)]
+    pub placement_strategy: PlacementStrategyKind,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last updated timestamp
@@ -42,6 +46,7 @@ impl DeploymentSpec {
             backend: BackendConfig::default(),
             health: HealthConfig::default(),
             autoscale: None,
+            placement_strategy: PlacementStrategyKind::default(),
             created_at: now,
             updated_at: now,
         }
@@ -57,6 +62,9 @@ pub struct ResourceRequirements {
     pub gpu_count: u32,
     /// Specific GPU IDs to use
     pub gpu_ids: Vec<u32>,
+    /// Placement constraints as `key=value` tags a node must carry
+    #[serde(default)]
+    pub placement_tags: Vec<String>,
 }
 
 impl Default for ResourceRequirements {
@@ -65,6 +73,34 @@ impl Default for ResourceRequirements {
             memory: None,
             gpu_count: 0,
             gpu_ids: Vec::new(),
+            placement_tags: Vec::new(),
+        }
+    }
+}
+
+/// GPU placement strategy for a deployment's workers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PlacementStrategyKind {
+    /// First-fit by GPU count, ignoring per-GPU memory (historical behavior).
+    #[default]
+    Default,
+    /// Consolidate onto the fullest GPUs that still fit, keeping whole GPUs free.
+    BinPack,
+    /// Prefer the emptiest, least-loaded GPUs to minimize contention.
+    Spread,
+    /// Weighted rendezvous (highest-random-weight) hashing, keeping a model
+    /// pinned to the same GPU(s) across reschedules.
+    Rendezvous,
+}
+
+impl std::fmt::Display for PlacementStrategyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlacementStrategyKind::Default => write!(f, "default"),
+            PlacementStrategyKind::BinPack => write!(f, "bin-pack"),
+            PlacementStrategyKind::Spread => write!(f, "spread"),
+            PlacementStrategyKind::Rendezvous => write!(f, "rendezvous"),
         }
     }
 }
@@ -150,6 +186,9 @@ pub struct AutoscaleConfig {
     pub target_cpu_utilization: Option<u32>,
     /// Target memory utilization percentage
     pub target_memory_utilization: Option<u32>,
+    /// Target GPU utilization percentage
+    #[serde(default)]
+    pub target_gpu_utilization: Option<u32>,
 }
 
 /// Worker represents a running inference server instance
@@ -171,10 +210,21 @@ pub struct Worker {
     pub container_id: Option<String>,
     /// Assigned GPU IDs
     pub gpu_ids: Vec<u32>,
+    /// Fractional GPU memory reservation ids held on behalf of this worker,
+    /// one per entry in `gpu_ids` when the deployment requested memory
+    #[serde(default)]
+    pub gpu_reservation_ids: Vec<Uuid>,
+    /// Cluster node this worker is placed on, when topology is configured
+    #[serde(default)]
+    pub node_id: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last health check timestamp
     pub last_health_check: Option<DateTime<Utc>>,
+    /// In-flight request count last reported over the heartbeat channel, used
+    /// to decide when a draining worker is safe to tear down
+    #[serde(default)]
+    pub active_requests: u32,
 }
 
 impl Worker {
@@ -189,8 +239,11 @@ impl Worker {
             pid: None,
             container_id: None,
             gpu_ids: Vec::new(),
+            gpu_reservation_ids: Vec::new(),
+            node_id: None,
             created_at: Utc::now(),
             last_health_check: None,
+            active_requests: 0,
         }
     }
 
@@ -212,10 +265,15 @@ pub enum WorkerStatus {
     Running,
     /// Worker is unhealthy
     Unhealthy,
+    /// Worker is finishing in-flight requests before it is removed; it no
+    /// longer receives new traffic but is not yet torn down
+    Draining,
     /// Worker is being terminated
     Terminating,
     /// Worker has terminated
     Terminated,
+    /// Worker missed its heartbeat deadline and is considered dead
+    Failed,
     /// Worker encountered an error
     Error,
 }
@@ -227,8 +285,10 @@ impl std::fmt::Display for WorkerStatus {
             WorkerStatus::Starting => write!(f, "Starting"),
             WorkerStatus::Running => write!(f, "Running"),
             WorkerStatus::Unhealthy => write!(f, "Unhealthy"),
+            WorkerStatus::Draining => write!(f, "Draining"),
             WorkerStatus::Terminating => write!(f, "Terminating"),
             WorkerStatus::Terminated => write!(f, "Terminated"),
+            WorkerStatus::Failed => write!(f, "Failed"),
             WorkerStatus::Error => write!(f, "Error"),
         }
     }
@@ -255,11 +315,25 @@ impl Endpoint {
         }
     }
 
-    /// Get the URL for this endpoint
+    /// Get the URL for this endpoint, using the literal host.
+    ///
+    /// This is for display and API responses, where clients expect the
+    /// original hostname rather than a resolved address. Use
+    /// [`Endpoint::resolved_url`] when actually dialing the endpoint.
     pub fn url(&self) -> String {
         let scheme = if self.tls { "https" } else { "http" };
         format!("{}://{}:{}", scheme, self.host, self.port)
     }
+
+    /// Get the URL for this endpoint, resolving the host via DoH first.
+    ///
+    /// Mirrors the health checker's pre-dial resolution so probes and proxied
+    /// requests work where local DNS is unreliable.
+    pub async fn resolved_url(&self, resolver: &Resolver) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        let host = resolver.resolve_one(&self.host).await;
+        format!("{}://{}:{}", scheme, host, self.port)
+    }
 }
 
 /// Deployment status summary
@@ -273,8 +347,13 @@ pub struct DeploymentStatus {
     pub ready_replicas: u32,
     /// Number of available replicas
     pub available_replicas: u32,
+    /// Number of replicas currently draining (finishing in-flight requests
+    /// before teardown)
+    pub draining_replicas: u32,
     /// Overall deployment phase
     pub phase: DeploymentPhase,
+    /// GPU placement strategy driving this deployment's workers
+    pub strategy: PlacementStrategyKind,
 }
 
 impl DeploymentStatus {
@@ -285,6 +364,10 @@ impl DeploymentStatus {
             .iter()
             .filter(|w| !matches!(w.status, WorkerStatus::Terminated | WorkerStatus::Error))
             .count() as u32;
+        let draining_replicas = workers
+            .iter()
+            .filter(|w| w.status == WorkerStatus::Draining)
+            .count() as u32;
 
         let phase = if ready_replicas == spec.replicas {
             DeploymentPhase::Ready
@@ -296,12 +379,16 @@ impl DeploymentStatus {
             DeploymentPhase::Progressing
         };
 
+        let strategy = spec.placement_strategy;
+
         Self {
             spec,
             workers,
             ready_replicas,
             available_replicas,
+            draining_replicas,
             phase,
+            strategy,
         }
     }
 }
@@ -0,0 +1,196 @@
+//! DNS-over-HTTPS name resolution
+//!
+//! A small resolver that resolves hostnames over HTTPS (RFC 8484 JSON mode)
+//! rather than the system resolver, which is valuable in locked-down or
+//! container environments where local DNS is unreliable. Results are cached
+//! in memory honoring the record TTL, and the resolver falls back to the
+//! system resolver when the DoH endpoint is unreachable.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Default DoH endpoint (Cloudflare).
+pub const DEFAULT_DOH_ENDPOINT: &str = "https://cloudflare-dns.com/dns-query";
+
+/// DNS record types we parse from the answer array.
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+
+/// A cached set of addresses with an expiry deadline.
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    addrs: Vec<IpAddr>,
+    expires_at: Instant,
+}
+
+/// A single answer record in a DoH JSON response.
+#[derive(Debug, Deserialize)]
+struct Answer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    #[serde(rename = "TTL")]
+    ttl: u64,
+    data: String,
+}
+
+/// A DoH JSON response.
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Answer", default)]
+    answer: Vec<Answer>,
+}
+
+/// Resolves hostnames via DNS-over-HTTPS with a system-resolver fallback.
+pub struct Resolver {
+    endpoint: String,
+    client: reqwest::Client,
+    cache: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl Resolver {
+    /// Create a resolver targeting the given DoH endpoint.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            client: reqwest::Client::new(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Resolve a hostname to a list of IP addresses.
+    ///
+    /// Literal IP addresses are returned verbatim. Results are served from the
+    /// in-memory cache while their TTL is valid; otherwise the DoH endpoint is
+    /// queried, and on failure the system resolver is used as a fallback.
+    pub async fn resolve(&self, host: &str) -> Vec<IpAddr> {
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return vec![ip];
+        }
+
+        if let Some(addrs) = self.cached(host) {
+            return addrs;
+        }
+
+        match self.query_doh(host).await {
+            Ok((addrs, ttl)) if !addrs.is_empty() => {
+                self.store(host, &addrs, ttl);
+                addrs
+            }
+            result => {
+                if let Err(e) = result {
+                    warn!(host = host, error = %e, "DoH resolution failed, falling back");
+                }
+                self.system_resolve(host)
+            }
+        }
+    }
+
+    /// Resolve a hostname and return the first address as a string, or the
+    /// original host if resolution yields nothing.
+    pub async fn resolve_one(&self, host: &str) -> String {
+        self.resolve(host)
+            .await
+            .first()
+            .map(|ip| ip.to_string())
+            .unwrap_or_else(|| host.to_string())
+    }
+
+    /// Look up a still-valid cache entry.
+    fn cached(&self, host: &str) -> Option<Vec<IpAddr>> {
+        let cache = self.cache.read().ok()?;
+        let entry = cache.get(host)?;
+        if entry.expires_at > Instant::now() {
+            Some(entry.addrs.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Store addresses in the cache, honoring the returned TTL.
+    fn store(&self, host: &str, addrs: &[IpAddr], ttl: u64) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.insert(
+                host.to_string(),
+                CacheEntry {
+                    addrs: addrs.to_vec(),
+                    expires_at: Instant::now() + Duration::from_secs(ttl.max(1)),
+                },
+            );
+        }
+    }
+
+    /// Issue an RFC 8484 JSON-mode query and parse the answer array.
+    async fn query_doh(&self, host: &str) -> Result<(Vec<IpAddr>, u64), reqwest::Error> {
+        let resp: DohResponse = self
+            .client
+            .get(&self.endpoint)
+            .query(&[("name", host), ("type", "A")])
+            .header("Accept", "application/dns-json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut addrs = Vec::new();
+        let mut min_ttl = u64::MAX;
+        for ans in resp.answer {
+            if ans.record_type == TYPE_A || ans.record_type == TYPE_AAAA {
+                if let Ok(ip) = ans.data.parse::<IpAddr>() {
+                    addrs.push(ip);
+                    min_ttl = min_ttl.min(ans.ttl);
+                }
+            }
+        }
+
+        let ttl = if min_ttl == u64::MAX { 30 } else { min_ttl };
+        debug!(host = host, count = addrs.len(), ttl, "Resolved via DoH");
+        Ok((addrs, ttl))
+    }
+
+    /// Fall back to the blocking system resolver.
+    fn system_resolve(&self, host: &str) -> Vec<IpAddr> {
+        (host, 0u16)
+            .to_socket_addrs()
+            .map(|addrs| addrs.map(|sa| sa.ip()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new(DEFAULT_DOH_ENDPOINT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_literal_ip() {
+        let resolver = Resolver::default();
+        let addrs = resolver.resolve("127.0.0.1").await;
+        assert_eq!(addrs, vec!["127.0.0.1".parse::<IpAddr>().unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_one_falls_back_to_host() {
+        let resolver = Resolver::new("https://invalid.endpoint.example/dns-query");
+        // A bogus TLD cannot resolve; we get the original host back.
+        let result = resolver.resolve_one("nonexistent.invalid").await;
+        assert_eq!(result, "nonexistent.invalid");
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let resolver = Resolver::default();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        resolver.store("example.com", &[ip], 60);
+        assert_eq!(resolver.cached("example.com"), Some(vec![ip]));
+    }
+}
@@ -58,8 +58,24 @@ async fn main() {
 
     info!("Starting dmrlet daemon v{}", env!("CARGO_PKG_VERSION"));
 
-    // Create scheduler
-    let scheduler = Arc::new(Scheduler::new(args.worker_base_port, args.max_workers));
+    // Create scheduler, persisting state to the configured database when set.
+    let store: Arc<dyn dmrlet_scheduler::StateStore> = match std::env::var("DMRLET_DATABASE_URL") {
+        Ok(url) => match dmrlet_scheduler::SqlStateStore::connect(&url).await {
+            Ok(store) => Arc::new(store),
+            Err(e) => {
+                tracing::warn!(error = %e, "Falling back to in-memory state store");
+                Arc::new(dmrlet_scheduler::MemoryStateStore::new())
+            }
+        },
+        Err(_) => Arc::new(dmrlet_scheduler::MemoryStateStore::new()),
+    };
+    let scheduler = Arc::new(Scheduler::new(args.worker_base_port, args.max_workers, store).await);
+
+    // Continuously drive actual worker state toward the desired replica count.
+    Arc::clone(&scheduler).spawn_reconciler(dmrlet_scheduler::DEFAULT_RECONCILE_INTERVAL);
+
+    // Periodically expire idle/TTL-exceeded models, independent of LRU.
+    scheduler.spawn_cache_expiry_sweeper(dmrlet_scheduler::DEFAULT_CACHE_EXPIRY_SWEEP_INTERVAL);
 
     // Create API router
     let router = create_router(scheduler);
@@ -1,15 +1,66 @@
 //! Load balancing strategies
 
+use crate::phi_accrual::SuspicionSet;
 use dmrlet_core::{Endpoint, LoadBalanceStrategy};
+use std::collections::HashMap;
+use std::ops::Deref;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use tracing::debug;
 
+/// RAII guard returned by [`LoadBalancer::select`].
+///
+/// The guard records one in-flight connection against the chosen endpoint: the
+/// endpoint's counter is incremented when the guard is created and decremented
+/// when it is dropped, so the balancer sees accurate concurrent load without
+/// the caller having to manage counters by hand.
+pub struct Connection<'a> {
+    endpoint: &'a Endpoint,
+    counter: Arc<AtomicUsize>,
+}
+
+impl<'a> Connection<'a> {
+    /// The endpoint this connection is bound to.
+    pub fn endpoint(&self) -> &'a Endpoint {
+        self.endpoint
+    }
+}
+
+impl Deref for Connection<'_> {
+    type Target = Endpoint;
+
+    fn deref(&self) -> &Endpoint {
+        self.endpoint
+    }
+}
+
+impl Drop for Connection<'_> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Stable metric label for a load balancing strategy.
+#[cfg(feature = "metrics")]
+fn strategy_label(strategy: LoadBalanceStrategy) -> &'static str {
+    match strategy {
+        LoadBalanceStrategy::RoundRobin => "round-robin",
+        LoadBalanceStrategy::LeastConnections => "least-connections",
+        LoadBalanceStrategy::PowerOfTwoChoices => "power-of-two-choices",
+        LoadBalanceStrategy::Random => "random",
+    }
+}
+
 /// Load balancer for distributing requests across workers
 pub struct LoadBalancer {
     /// Load balancing strategy
     strategy: LoadBalanceStrategy,
     /// Counter for round-robin
     counter: AtomicUsize,
+    /// Endpoints suspected by the phi-accrual detector, excluded from selection
+    suspicions: Option<Arc<SuspicionSet>>,
+    /// Live connection counts per endpoint, keyed by `host:port`.
+    connections: Mutex<HashMap<String, Arc<AtomicUsize>>>,
 }
 
 impl LoadBalancer {
@@ -18,36 +69,127 @@ impl LoadBalancer {
         Self {
             strategy,
             counter: AtomicUsize::new(0),
+            suspicions: None,
+            connections: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Select an endpoint from the list
-    pub fn select<'a>(&self, endpoints: &'a [Endpoint]) -> Option<&'a Endpoint> {
+    /// Attach a shared suspicion set so suspected endpoints are skipped.
+    pub fn with_suspicions(mut self, suspicions: Arc<SuspicionSet>) -> Self {
+        self.suspicions = Some(suspicions);
+        self
+    }
+
+    /// Whether an endpoint is currently suspected.
+    fn is_suspected(&self, endpoint: &Endpoint) -> bool {
+        match &self.suspicions {
+            Some(set) => set.is_suspected(&Self::key(endpoint)),
+            None => false,
+        }
+    }
+
+    /// Stable key used to track an endpoint's connection count.
+    fn key(endpoint: &Endpoint) -> String {
+        format!("{}:{}", endpoint.host, endpoint.port)
+    }
+
+    /// Look up (creating if necessary) the connection counter for an endpoint.
+    fn counter_for(&self, endpoint: &Endpoint) -> Arc<AtomicUsize> {
+        let mut conns = self.connections.lock().unwrap();
+        conns
+            .entry(Self::key(endpoint))
+            .or_insert_with(|| Arc::new(AtomicUsize::new(0)))
+            .clone()
+    }
+
+    /// Current number of in-flight connections for an endpoint.
+    pub fn active_connections(&self, endpoint: &Endpoint) -> usize {
+        let conns = self.connections.lock().unwrap();
+        conns
+            .get(&Self::key(endpoint))
+            .map(|c| c.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Select an endpoint and acquire a connection against it.
+    ///
+    /// The returned [`Connection`] guard tracks the in-flight request: the
+    /// endpoint's connection count is incremented here and decremented when the
+    /// guard is dropped. Returns `None` if there are no endpoints to choose
+    /// from.
+    pub fn select<'a>(&self, endpoints: &'a [Endpoint]) -> Option<Connection<'a>> {
         if endpoints.is_empty() {
             return None;
         }
 
-        let index = match self.strategy {
+        // Prefer trusted endpoints; fall back to the full set only if every
+        // endpoint is currently suspected, so the balancer never fails closed.
+        let candidates: Vec<usize> = {
+            let trusted: Vec<usize> = endpoints
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| !self.is_suspected(e))
+                .map(|(i, _)| i)
+                .collect();
+            if trusted.is_empty() {
+                (0..endpoints.len()).collect()
+            } else {
+                trusted
+            }
+        };
+
+        let slot = match self.strategy {
             LoadBalanceStrategy::RoundRobin => {
-                let idx = self.counter.fetch_add(1, Ordering::Relaxed) % endpoints.len();
-                idx
+                self.counter.fetch_add(1, Ordering::Relaxed) % candidates.len()
             }
             LoadBalanceStrategy::Random => {
-                use std::time::{SystemTime, UNIX_EPOCH};
-                let seed = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .subsec_nanos() as usize;
-                seed % endpoints.len()
+                rand::random::<usize>() % candidates.len()
             }
             LoadBalanceStrategy::LeastConnections => {
-                // For now, fallback to round-robin
-                // A real implementation would track connection counts
-                let idx = self.counter.fetch_add(1, Ordering::Relaxed) % endpoints.len();
-                idx
+                // Scan every candidate and pick the one with the fewest
+                // in-flight connections, breaking ties by order.
+                candidates
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, &idx)| self.active_connections(&endpoints[idx]))
+                    .map(|(slot, _)| slot)
+                    .unwrap_or(0)
+            }
+            LoadBalanceStrategy::PowerOfTwoChoices => {
+                // Sample two distinct candidates at random and keep whichever
+                // has fewer active connections. This approaches the balance of
+                // a full least-connections scan at O(1) sampling cost.
+                let a = rand::random::<usize>() % candidates.len();
+                let b = if candidates.len() == 1 {
+                    a
+                } else {
+                    let mut b = rand::random::<usize>() % (candidates.len() - 1);
+                    if b >= a {
+                        b += 1;
+                    }
+                    b
+                };
+                let load_a = self.active_connections(&endpoints[candidates[a]]);
+                let load_b = self.active_connections(&endpoints[candidates[b]]);
+                if load_b < load_a {
+                    b
+                } else {
+                    a
+                }
             }
         };
 
+        let index = candidates[slot];
+        let endpoint = &endpoints[index];
+        let counter = self.counter_for(endpoint);
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::counter_inc(
+            "dmrlet_lb_selections_total",
+            &[("strategy", strategy_label(self.strategy))],
+        );
+
         debug!(
             strategy = ?self.strategy,
             selected_index = index,
@@ -55,7 +197,7 @@ impl LoadBalancer {
             "Selected endpoint"
         );
 
-        endpoints.get(index)
+        Some(Connection { endpoint, counter })
     }
 
     /// Get the current strategy
@@ -105,4 +247,29 @@ mod tests {
 
         assert!(lb.select(&endpoints).is_none());
     }
+
+    #[test]
+    fn test_connection_guard_tracks_load() {
+        let lb = LoadBalancer::default();
+        let endpoints = create_test_endpoints();
+
+        {
+            let _c = lb.select(&endpoints).unwrap();
+            assert_eq!(lb.active_connections(&endpoints[0]), 1);
+        }
+        // The guard was dropped, so the count falls back to zero.
+        assert_eq!(lb.active_connections(&endpoints[0]), 0);
+    }
+
+    #[test]
+    fn test_least_connections_prefers_idle() {
+        let lb = LoadBalancer::new(LoadBalanceStrategy::LeastConnections);
+        let endpoints = create_test_endpoints();
+
+        // Pin a connection on the first endpoint; the next selection should
+        // avoid it in favour of an idle endpoint.
+        let _held = lb.select(&endpoints).unwrap();
+        let next = lb.select(&endpoints).unwrap();
+        assert_ne!(next.port, _held.port);
+    }
 }
@@ -1,8 +1,11 @@
 //! Service discovery for dmrlet
 
+use crate::gossip::{ClusterView, EndpointEvent};
 use dmrlet_core::Endpoint;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tokio::sync::RwLock;
 use tracing::debug;
 use uuid::Uuid;
@@ -10,16 +13,55 @@ use uuid::Uuid;
 type EndpointsMap = HashMap<Uuid, Vec<Endpoint>>;
 
 /// Service discovery registry
+///
+/// The local map is always maintained as a fast cache for this node's own
+/// endpoints. When gossip is enabled, a [`ClusterView`] merged from peer
+/// events provides the cluster-wide picture and outgoing events are forwarded
+/// to the swarm over a channel.
 pub struct ServiceDiscovery {
-    /// Endpoints indexed by deployment ID
+    /// Endpoints indexed by deployment ID (this node's local cache)
     endpoints: Arc<RwLock<EndpointsMap>>,
+    /// Cluster-wide view merged from gossip, if enabled
+    cluster: Option<Arc<RwLock<ClusterView>>>,
+    /// Sender for outgoing gossip events, if enabled
+    gossip_tx: Option<mpsc::UnboundedSender<EndpointEvent>>,
+    /// Monotonic version counter for last-writer-wins ordering
+    version: AtomicU64,
 }
 
 impl ServiceDiscovery {
-    /// Create a new service discovery registry
+    /// Create a new single-process service discovery registry
     pub fn new() -> Self {
         Self {
             endpoints: Arc::new(RwLock::new(HashMap::new())),
+            cluster: None,
+            gossip_tx: None,
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a registry backed by a shared cluster view and gossip channel.
+    pub fn with_gossip(
+        cluster: Arc<RwLock<ClusterView>>,
+        gossip_tx: mpsc::UnboundedSender<EndpointEvent>,
+    ) -> Self {
+        Self {
+            endpoints: Arc::new(RwLock::new(HashMap::new())),
+            cluster: Some(cluster),
+            gossip_tx: Some(gossip_tx),
+            version: AtomicU64::new(0),
+        }
+    }
+
+    /// Next logical version for an outgoing event.
+    fn next_version(&self) -> u64 {
+        self.version.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Apply an event received from a peer to the cluster view.
+    pub async fn apply_remote(&self, event: EndpointEvent) {
+        if let Some(cluster) = &self.cluster {
+            cluster.write().await.merge(&event);
         }
     }
 
@@ -36,33 +78,68 @@ impl ServiceDiscovery {
             endpoint = %endpoint.url(),
             "Registered endpoint"
         );
+        drop(endpoints);
+
+        if let (Some(cluster), Some(tx)) = (&self.cluster, &self.gossip_tx) {
+            let event = EndpointEvent::Register {
+                deployment_id,
+                endpoint,
+                version: self.next_version(),
+            };
+            cluster.write().await.merge(&event);
+            let _ = tx.send(event);
+        }
     }
 
     /// Unregister an endpoint for a deployment
     pub async fn unregister(&self, deployment_id: Uuid, port: u16) {
-        let mut endpoints = self.endpoints.write().await;
-        if let Some(eps) = endpoints.get_mut(&deployment_id) {
-            eps.retain(|e| e.port != port);
-            if eps.is_empty() {
-                endpoints.remove(&deployment_id);
+        let host = {
+            let mut endpoints = self.endpoints.write().await;
+            let mut host = None;
+            if let Some(eps) = endpoints.get_mut(&deployment_id) {
+                if let Some(ep) = eps.iter().find(|e| e.port == port) {
+                    host = Some(ep.host.clone());
+                }
+                eps.retain(|e| e.port != port);
+                if eps.is_empty() {
+                    endpoints.remove(&deployment_id);
+                }
             }
-        }
+            host
+        };
 
         debug!(
             deployment_id = %deployment_id,
             port = port,
             "Unregistered endpoint"
         );
+
+        if let (Some(cluster), Some(tx), Some(host)) = (&self.cluster, &self.gossip_tx, host) {
+            let event = EndpointEvent::Unregister {
+                deployment_id,
+                host,
+                port,
+                version: self.next_version(),
+            };
+            cluster.write().await.merge(&event);
+            let _ = tx.send(event);
+        }
     }
 
-    /// Get all endpoints for a deployment
+    /// Get all endpoints for a deployment (cluster-wide when gossip is enabled)
     pub async fn get_endpoints(&self, deployment_id: Uuid) -> Vec<Endpoint> {
+        if let Some(cluster) = &self.cluster {
+            return cluster.read().await.endpoints_for(deployment_id);
+        }
         let endpoints = self.endpoints.read().await;
         endpoints.get(&deployment_id).cloned().unwrap_or_default()
     }
 
-    /// Get all endpoints across all deployments
+    /// Get all endpoints across all deployments (cluster-wide when enabled)
     pub async fn get_all_endpoints(&self) -> Vec<Endpoint> {
+        if let Some(cluster) = &self.cluster {
+            return cluster.read().await.endpoints();
+        }
         let endpoints = self.endpoints.read().await;
         endpoints.values().flatten().cloned().collect()
     }
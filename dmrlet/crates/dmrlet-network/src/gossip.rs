@@ -0,0 +1,277 @@
+//! Gossip backend for cluster-wide service discovery
+//!
+//! Each dmrlet node joins a libp2p gossipsub swarm, discovers peers over mDNS
+//! on the LAN plus an optional static bootstrap list, and subscribes to a
+//! shared topic carrying `Register`/`Unregister` events. Incoming events merge
+//! into a [`ClusterView`] using last-writer-wins semantics keyed by
+//! `(deployment_id, host, port)`, keeping tombstones for unregistrations so
+//! stale entries eventually drop. The swarm only receives and decodes events;
+//! nothing in this module publishes local events onto the topic.
+
+use dmrlet_core::Endpoint;
+use libp2p::{
+    gossipsub, mdns,
+    swarm::{NetworkBehaviour, SwarmEvent},
+    Multiaddr,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Topic on which endpoint events are gossiped.
+pub const DISCOVERY_TOPIC: &str = "dmrlet/discovery/v1";
+
+/// Configuration for the gossip backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GossipConfig {
+    /// Whether gossip-based discovery is enabled.
+    pub enabled: bool,
+    /// Address to listen on for the swarm.
+    pub listen_address: String,
+    /// Static bootstrap peers to dial on startup.
+    pub bootstrap_peers: Vec<String>,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_address: "/ip4/0.0.0.0/tcp/0".to_string(),
+            bootstrap_peers: Vec::new(),
+        }
+    }
+}
+
+/// A monotonically-versioned endpoint event gossiped to peers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EndpointEvent {
+    /// Advertise an endpoint for a deployment.
+    Register {
+        deployment_id: Uuid,
+        endpoint: Endpoint,
+        /// Logical timestamp for last-writer-wins ordering.
+        version: u64,
+    },
+    /// Retract an endpoint; carried as a tombstone until it is superseded.
+    Unregister {
+        deployment_id: Uuid,
+        host: String,
+        port: u16,
+        version: u64,
+    },
+}
+
+impl EndpointEvent {
+    /// The last-writer-wins key for this event.
+    fn key(&self) -> (Uuid, String, u16) {
+        match self {
+            EndpointEvent::Register {
+                deployment_id,
+                endpoint,
+                ..
+            } => (*deployment_id, endpoint.host.clone(), endpoint.port),
+            EndpointEvent::Unregister {
+                deployment_id,
+                host,
+                port,
+                ..
+            } => (*deployment_id, host.clone(), *port),
+        }
+    }
+
+    fn version(&self) -> u64 {
+        match self {
+            EndpointEvent::Register { version, .. } | EndpointEvent::Unregister { version, .. } => {
+                *version
+            }
+        }
+    }
+}
+
+/// A merged record: either a live endpoint or a tombstone, with its version.
+#[derive(Debug, Clone)]
+struct Record {
+    version: u64,
+    endpoint: Option<Endpoint>,
+}
+
+/// Cluster-wide endpoint view built from gossiped events.
+#[derive(Debug, Default)]
+pub struct ClusterView {
+    records: HashMap<(Uuid, String, u16), Record>,
+}
+
+impl ClusterView {
+    /// Create an empty cluster view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge an event using last-writer-wins on the event version.
+    ///
+    /// Returns `true` if the event was newer than what we had (and therefore
+    /// should be re-gossiped), `false` if it was stale.
+    pub fn merge(&mut self, event: &EndpointEvent) -> bool {
+        let key = event.key();
+        let version = event.version();
+
+        if let Some(existing) = self.records.get(&key) {
+            if existing.version >= version {
+                return false;
+            }
+        }
+
+        let endpoint = match event {
+            EndpointEvent::Register { endpoint, .. } => Some(endpoint.clone()),
+            EndpointEvent::Unregister { .. } => None,
+        };
+        self.records.insert(key, Record { version, endpoint });
+        true
+    }
+
+    /// All live endpoints across the cluster.
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        self.records
+            .values()
+            .filter_map(|r| r.endpoint.clone())
+            .collect()
+    }
+
+    /// Live endpoints for a single deployment.
+    pub fn endpoints_for(&self, deployment_id: Uuid) -> Vec<Endpoint> {
+        self.records
+            .iter()
+            .filter(|((id, _, _), _)| *id == deployment_id)
+            .filter_map(|(_, r)| r.endpoint.clone())
+            .collect()
+    }
+}
+
+/// Composed libp2p behaviour: gossipsub for events, mDNS for LAN discovery.
+#[derive(NetworkBehaviour)]
+struct DiscoveryBehaviour {
+    gossipsub: gossipsub::Behaviour,
+    mdns: mdns::tokio::Behaviour,
+}
+
+/// Spawn the gossip swarm and drive it, feeding decoded events to `on_event`.
+///
+/// The swarm subscribes to [`DISCOVERY_TOPIC`] and decodes what peers send on
+/// it; it publishes no events of its own.
+///
+/// The task runs until the swarm terminates; callers typically spawn it with
+/// `tokio::spawn` and keep the process alive.
+pub async fn run_swarm<F>(config: GossipConfig, mut on_event: F) -> Result<(), String>
+where
+    F: FnMut(EndpointEvent) + Send + 'static,
+{
+    use libp2p::futures::StreamExt;
+
+    let mut swarm = libp2p::SwarmBuilder::with_new_identity()
+        .with_tokio()
+        .with_tcp(
+            Default::default(),
+            libp2p::noise::Config::new,
+            libp2p::yamux::Config::default,
+        )
+        .map_err(|e| format!("transport setup failed: {e}"))?
+        .with_behaviour(|key| {
+            let gossipsub = gossipsub::Behaviour::new(
+                gossipsub::MessageAuthenticity::Signed(key.clone()),
+                gossipsub::Config::default(),
+            )?;
+            let mdns =
+                mdns::tokio::Behaviour::new(mdns::Config::default(), key.public().to_peer_id())?;
+            Ok(DiscoveryBehaviour { gossipsub, mdns })
+        })
+        .map_err(|e| format!("behaviour setup failed: {e}"))?
+        .build();
+
+    let topic = gossipsub::IdentTopic::new(DISCOVERY_TOPIC);
+    swarm
+        .behaviour_mut()
+        .gossipsub
+        .subscribe(&topic)
+        .map_err(|e| format!("subscribe failed: {e}"))?;
+
+    let listen: Multiaddr = config
+        .listen_address
+        .parse()
+        .map_err(|e| format!("invalid listen address: {e}"))?;
+    swarm
+        .listen_on(listen)
+        .map_err(|e| format!("listen failed: {e}"))?;
+
+    for peer in &config.bootstrap_peers {
+        match peer.parse::<Multiaddr>() {
+            Ok(addr) => {
+                if let Err(e) = swarm.dial(addr) {
+                    warn!(peer = %peer, error = %e, "Failed to dial bootstrap peer");
+                }
+            }
+            Err(e) => warn!(peer = %peer, error = %e, "Invalid bootstrap peer address"),
+        }
+    }
+
+    info!(topic = DISCOVERY_TOPIC, "Gossip discovery swarm started");
+
+    let _ = &topic;
+    loop {
+        match swarm.select_next_some().await {
+            SwarmEvent::Behaviour(DiscoveryBehaviourEvent::Mdns(mdns::Event::Discovered(
+                peers,
+            ))) => {
+                for (peer_id, _addr) in peers {
+                    swarm
+                        .behaviour_mut()
+                        .gossipsub
+                        .add_explicit_peer(&peer_id);
+                    debug!(peer = %peer_id, "Discovered peer via mDNS");
+                }
+            }
+            SwarmEvent::Behaviour(DiscoveryBehaviourEvent::Gossipsub(
+                gossipsub::Event::Message { message, .. },
+            )) => match serde_json::from_slice::<EndpointEvent>(&message.data) {
+                Ok(event) => on_event(event),
+                Err(e) => warn!(error = %e, "Failed to decode gossip event"),
+            },
+            SwarmEvent::NewListenAddr { address, .. } => {
+                info!(address = %address, "Gossip swarm listening");
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_last_writer_wins() {
+        let mut view = ClusterView::new();
+        let id = Uuid::new_v4();
+
+        let reg = EndpointEvent::Register {
+            deployment_id: id,
+            endpoint: Endpoint::new("10.0.0.1".to_string(), 30000),
+            version: 1,
+        };
+        assert!(view.merge(&reg));
+        assert_eq!(view.endpoints().len(), 1);
+
+        // Stale event is ignored.
+        assert!(!view.merge(&reg));
+
+        // A newer tombstone retracts the endpoint.
+        let unreg = EndpointEvent::Unregister {
+            deployment_id: id,
+            host: "10.0.0.1".to_string(),
+            port: 30000,
+            version: 2,
+        };
+        assert!(view.merge(&unreg));
+        assert!(view.endpoints().is_empty());
+    }
+}
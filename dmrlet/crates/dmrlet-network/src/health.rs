@@ -1,7 +1,11 @@
 //! Health checking for workers
 
-use dmrlet_core::Endpoint;
-use std::time::Duration;
+use crate::phi_accrual::{PhiAccrual, SuspicionSet, DEFAULT_PHI_THRESHOLD};
+use dmrlet_core::{Endpoint, Resolver};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
 /// Health checker for workers
@@ -12,11 +16,24 @@ pub struct HealthChecker {
     health_path: String,
     /// Timeout duration
     timeout: Duration,
+    /// Resolver used to resolve endpoint hosts before probing
+    resolver: Arc<Resolver>,
+    /// Phi threshold above which an endpoint is suspected
+    phi_threshold: f64,
+    /// Per-endpoint phi-accrual detectors, keyed by host:port
+    detectors: Mutex<HashMap<String, PhiAccrual>>,
+    /// Shared view of currently-suspected endpoints for the load balancer
+    suspicions: Arc<SuspicionSet>,
 }
 
 impl HealthChecker {
     /// Create a new health checker
     pub fn new(health_path: String, timeout_secs: u64) -> Self {
+        Self::with_resolver(health_path, timeout_secs, Arc::new(Resolver::default()))
+    }
+
+    /// Create a health checker with a shared resolver
+    pub fn with_resolver(health_path: String, timeout_secs: u64, resolver: Arc<Resolver>) -> Self {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(timeout_secs))
             .build()
@@ -26,17 +43,45 @@ impl HealthChecker {
             client,
             health_path,
             timeout: Duration::from_secs(timeout_secs),
+            resolver,
+            phi_threshold: DEFAULT_PHI_THRESHOLD,
+            detectors: Mutex::new(HashMap::new()),
+            suspicions: Arc::new(SuspicionSet::new()),
         }
     }
 
+    /// Override the phi suspicion threshold (default [`DEFAULT_PHI_THRESHOLD`]).
+    pub fn with_phi_threshold(mut self, threshold: f64) -> Self {
+        self.phi_threshold = threshold;
+        self
+    }
+
+    /// Shared suspicion set to hand to a [`crate::balancer::LoadBalancer`].
+    pub fn suspicions(&self) -> Arc<SuspicionSet> {
+        Arc::clone(&self.suspicions)
+    }
+
+    /// Endpoint key used for detector and suspicion bookkeeping.
+    fn endpoint_key(endpoint: &Endpoint) -> String {
+        format!("{}:{}", endpoint.host, endpoint.port)
+    }
+
     /// Check the health of an endpoint
     pub async fn check(&self, endpoint: &Endpoint) -> bool {
-        let url = format!("{}{}", endpoint.url(), self.health_path);
+        // Resolve the host via DoH so probes work where local DNS is unreliable.
+        let host = self.resolver.resolve_one(&endpoint.host).await;
+        let scheme = if endpoint.tls { "https" } else { "http" };
+        let url = format!(
+            "{}://{}:{}{}",
+            scheme, host, endpoint.port, self.health_path
+        );
 
-        match self.client.get(&url).send().await {
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+        let healthy = match self.client.get(&url).send().await {
             Ok(response) => {
-                let healthy = response.status().is_success();
-                if healthy {
+                let ok = response.status().is_success();
+                if ok {
                     debug!(endpoint = %url, "Health check passed");
                 } else {
                     warn!(
@@ -45,7 +90,7 @@ impl HealthChecker {
                         "Health check failed"
                     );
                 }
-                healthy
+                ok
             }
             Err(e) => {
                 warn!(
@@ -55,7 +100,50 @@ impl HealthChecker {
                 );
                 false
             }
+        };
+
+        #[cfg(feature = "metrics")]
+        {
+            let result = if healthy { "success" } else { "failure" };
+            dmrlet_core::metrics::counter_inc("dmrlet_health_checks_total", &[("result", result)]);
+            dmrlet_core::metrics::histogram_observe(
+                "dmrlet_health_check_latency_seconds",
+                &[],
+                started.elapsed().as_secs_f64(),
+            );
+        }
+
+        // A successful probe is a heartbeat; recompute phi and publish the
+        // resulting suspicion so the balancer can react to degrading latency
+        // rather than to this single pass/fail.
+        let key = Self::endpoint_key(endpoint);
+        let phi = {
+            let mut detectors = self.detectors.lock().await;
+            let detector = detectors
+                .entry(key.clone())
+                .or_insert_with(|| PhiAccrual::new(self.timeout.as_millis() as f64));
+            let now = Instant::now();
+            if healthy {
+                detector.heartbeat(now);
+            }
+            detector.phi(now)
+        };
+
+        let suspected = phi > self.phi_threshold;
+        self.suspicions.set(&key, suspected);
+        if suspected {
+            warn!(endpoint = %url, phi = phi, "Endpoint suspected by phi-accrual detector");
         }
+
+        healthy
+    }
+
+    /// Current phi suspicion value for an endpoint, if it has been probed.
+    pub async fn phi(&self, endpoint: &Endpoint) -> Option<f64> {
+        let detectors = self.detectors.lock().await;
+        detectors
+            .get(&Self::endpoint_key(endpoint))
+            .map(|d| d.phi(Instant::now()))
     }
 
     /// Get the timeout duration
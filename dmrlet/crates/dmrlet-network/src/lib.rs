@@ -7,8 +7,16 @@
 
 pub mod balancer;
 pub mod discovery;
+pub mod gossip;
 pub mod health;
+pub mod membership;
+pub mod phi_accrual;
+pub mod supervisor;
 
 pub use balancer::LoadBalancer;
 pub use discovery::ServiceDiscovery;
+pub use gossip::{ClusterView, EndpointEvent, GossipConfig};
 pub use health::HealthChecker;
+pub use membership::{Member, MemberState, MemberUpdate, Membership};
+pub use phi_accrual::{PhiAccrual, SuspicionSet};
+pub use supervisor::{BackoffPolicy, HealthSupervisor, WorkerControl};
@@ -0,0 +1,304 @@
+//! SWIM-style cluster membership
+//!
+//! Several `dmrletd` instances form one cluster using the SWIM protocol: each
+//! node keeps a member list with incarnation numbers and, on a periodic tick,
+//! probes one random member. A missed ack escalates to indirect ping-requests
+//! through `k` random members; if those also fail the target is marked
+//! `Suspect`, then `Dead` after a grace period. Membership deltas are
+//! piggybacked on ping/ack payloads in infection style so they propagate
+//! without a central coordinator; a node refutes a false suspicion by bumping
+//! its own incarnation number.
+
+use dmrlet_core::ClusterConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Liveness state of a cluster member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemberState {
+    /// Responding to probes.
+    Alive,
+    /// Missed a probe; pending confirmation.
+    Suspect,
+    /// Declared dead after the suspect grace period.
+    Dead,
+}
+
+/// A single cluster member.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Member {
+    /// Stable node identifier.
+    pub id: String,
+    /// Gossip/RPC address (host:port).
+    pub address: String,
+    /// Current liveness state.
+    pub state: MemberState,
+    /// Incarnation number; higher always wins.
+    pub incarnation: u64,
+    /// When this member entered its current state (local clock).
+    #[serde(skip, default = "Instant::now")]
+    pub state_since: Instant,
+}
+
+/// A membership update gossiped between nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemberUpdate {
+    pub id: String,
+    pub address: String,
+    pub state: MemberState,
+    pub incarnation: u64,
+}
+
+/// The local view of cluster membership.
+pub struct Membership {
+    local_id: String,
+    local_incarnation: u64,
+    members: HashMap<String, Member>,
+    config: ClusterConfig,
+}
+
+impl Membership {
+    /// Create a membership table seeded with the configured peers.
+    pub fn new(local_id: impl Into<String>, local_address: impl Into<String>, config: ClusterConfig) -> Self {
+        let local_id = local_id.into();
+        let mut members = HashMap::new();
+        members.insert(
+            local_id.clone(),
+            Member {
+                id: local_id.clone(),
+                address: local_address.into(),
+                state: MemberState::Alive,
+                incarnation: 0,
+                state_since: Instant::now(),
+            },
+        );
+
+        for (i, peer) in config.peers.iter().enumerate() {
+            let id = format!("seed-{}", i);
+            members.insert(
+                id.clone(),
+                Member {
+                    id,
+                    address: peer.clone(),
+                    state: MemberState::Alive,
+                    incarnation: 0,
+                    state_since: Instant::now(),
+                },
+            );
+        }
+
+        Self {
+            local_id,
+            local_incarnation: 0,
+            members,
+            config,
+        }
+    }
+
+    /// Live members (Alive or Suspect) other than ourselves.
+    pub fn live_members(&self) -> Vec<Member> {
+        self.members
+            .values()
+            .filter(|m| m.id != self.local_id && m.state != MemberState::Dead)
+            .cloned()
+            .collect()
+    }
+
+    /// Merge an incoming update using incarnation ordering.
+    ///
+    /// A higher incarnation always wins. At equal incarnation, a worse state
+    /// (Alive < Suspect < Dead) wins so bad news spreads. If the update
+    /// suspects or kills *us* at our current incarnation, we refute it by
+    /// bumping our own incarnation and re-asserting `Alive`.
+    pub fn apply(&mut self, update: &MemberUpdate) {
+        if update.id == self.local_id {
+            if update.state != MemberState::Alive && update.incarnation >= self.local_incarnation {
+                self.local_incarnation = update.incarnation + 1;
+                if let Some(me) = self.members.get_mut(&self.local_id) {
+                    me.state = MemberState::Alive;
+                    me.incarnation = self.local_incarnation;
+                }
+                info!(
+                    incarnation = self.local_incarnation,
+                    "Refuting false suspicion"
+                );
+            }
+            return;
+        }
+
+        match self.members.get_mut(&update.id) {
+            Some(existing) => {
+                let newer = update.incarnation > existing.incarnation;
+                let same_worse = update.incarnation == existing.incarnation
+                    && rank(update.state) > rank(existing.state);
+                if newer || same_worse {
+                    existing.state = update.state;
+                    existing.incarnation = update.incarnation;
+                    existing.state_since = Instant::now();
+                    debug!(member = %update.id, state = ?update.state, "Applied membership update");
+                }
+            }
+            None => {
+                self.members.insert(
+                    update.id.clone(),
+                    Member {
+                        id: update.id.clone(),
+                        address: update.address.clone(),
+                        state: update.state,
+                        incarnation: update.incarnation,
+                        state_since: Instant::now(),
+                    },
+                );
+                info!(member = %update.id, "Discovered new member");
+            }
+        }
+    }
+
+    /// Mark a member suspect after a failed direct + indirect probe.
+    pub fn suspect(&mut self, id: &str) {
+        if let Some(m) = self.members.get_mut(id) {
+            if m.state == MemberState::Alive {
+                m.state = MemberState::Suspect;
+                m.state_since = Instant::now();
+                warn!(member = %id, "Member suspected");
+            }
+        }
+    }
+
+    /// Confirm a member alive (acked a probe).
+    pub fn confirm_alive(&mut self, id: &str, incarnation: u64) {
+        if let Some(m) = self.members.get_mut(id) {
+            if incarnation >= m.incarnation {
+                m.state = MemberState::Alive;
+                m.incarnation = incarnation;
+                m.state_since = Instant::now();
+            }
+        }
+    }
+
+    /// Promote suspects that have exceeded the grace period to `Dead`.
+    pub fn reap_suspects(&mut self) {
+        let grace = Duration::from_millis(self.config.suspect_timeout_ms);
+        let now = Instant::now();
+        for m in self.members.values_mut() {
+            if m.state == MemberState::Suspect && now.duration_since(m.state_since) >= grace {
+                m.state = MemberState::Dead;
+                m.state_since = now;
+                warn!(member = %m.id, "Member declared dead");
+            }
+        }
+    }
+
+    /// Pick a random member to probe on this tick, excluding ourselves and
+    /// already-dead members. Returns `None` when we are alone in the cluster.
+    pub fn probe_target(&self) -> Option<Member> {
+        let candidates: Vec<&Member> = self
+            .members
+            .values()
+            .filter(|m| m.id != self.local_id && m.state != MemberState::Dead)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let idx = rand::random::<usize>() % candidates.len();
+        Some(candidates[idx].clone())
+    }
+
+    /// Pick up to `indirect_probes` random members to relay an indirect
+    /// ping-request to, excluding ourselves and the unreachable target.
+    pub fn indirect_relays(&self, target: &str) -> Vec<Member> {
+        let mut candidates: Vec<Member> = self
+            .members
+            .values()
+            .filter(|m| m.id != self.local_id && m.id != target && m.state == MemberState::Alive)
+            .cloned()
+            .collect();
+
+        // Fisher-Yates partial shuffle to take k without bias.
+        let k = self.config.indirect_probes.min(candidates.len());
+        for i in 0..k {
+            let j = i + rand::random::<usize>() % (candidates.len() - i);
+            candidates.swap(i, j);
+        }
+        candidates.truncate(k);
+        candidates
+    }
+
+    /// Snapshot of the full member list for piggybacking on a probe.
+    pub fn updates(&self) -> Vec<MemberUpdate> {
+        self.members
+            .values()
+            .map(|m| MemberUpdate {
+                id: m.id.clone(),
+                address: m.address.clone(),
+                state: m.state,
+                incarnation: m.incarnation,
+            })
+            .collect()
+    }
+}
+
+/// Severity rank used for equal-incarnation tie-breaking.
+fn rank(state: MemberState) -> u8 {
+    match state {
+        MemberState::Alive => 0,
+        MemberState::Suspect => 1,
+        MemberState::Dead => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn membership() -> Membership {
+        Membership::new("node-a", "127.0.0.1:9000", ClusterConfig::default())
+    }
+
+    #[test]
+    fn test_apply_new_member() {
+        let mut m = membership();
+        m.apply(&MemberUpdate {
+            id: "node-b".to_string(),
+            address: "10.0.0.2:9000".to_string(),
+            state: MemberState::Alive,
+            incarnation: 1,
+        });
+        assert!(m.live_members().iter().any(|x| x.id == "node-b"));
+    }
+
+    #[test]
+    fn test_refute_false_suspicion() {
+        let mut m = membership();
+        m.apply(&MemberUpdate {
+            id: "node-a".to_string(),
+            address: "127.0.0.1:9000".to_string(),
+            state: MemberState::Suspect,
+            incarnation: 0,
+        });
+        // We bumped our incarnation and stayed Alive.
+        assert_eq!(m.local_incarnation, 1);
+        assert_eq!(m.members["node-a"].state, MemberState::Alive);
+    }
+
+    #[test]
+    fn test_higher_incarnation_wins() {
+        let mut m = membership();
+        m.apply(&MemberUpdate {
+            id: "node-b".to_string(),
+            address: "10.0.0.2:9000".to_string(),
+            state: MemberState::Suspect,
+            incarnation: 5,
+        });
+        m.apply(&MemberUpdate {
+            id: "node-b".to_string(),
+            address: "10.0.0.2:9000".to_string(),
+            state: MemberState::Alive,
+            incarnation: 6,
+        });
+        assert_eq!(m.members["node-b"].state, MemberState::Alive);
+    }
+}
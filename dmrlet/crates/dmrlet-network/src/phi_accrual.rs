@@ -0,0 +1,181 @@
+//! Phi-accrual adaptive failure detection
+//!
+//! Instead of a fixed timeout, each endpoint accrues a suspicion value `phi`
+//! from the statistical distribution of its recent heartbeat inter-arrival
+//! times. `phi = -log10(P(now - last_heartbeat))`, where `P` is the tail of a
+//! normal distribution fit to the sampled intervals, so a network that is
+//! simply slow but consistent stays trusted while one that has genuinely
+//! stalled climbs past the threshold quickly.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::RwLock;
+use std::time::Instant;
+
+/// Maximum number of interval samples retained per endpoint.
+const MAX_SAMPLES: usize = 200;
+/// Minimum standard deviation (ms) to avoid division by zero.
+const MIN_STD_DEV_MS: f64 = 10.0;
+/// Default suspicion threshold.
+pub const DEFAULT_PHI_THRESHOLD: f64 = 8.0;
+
+/// Per-endpoint phi-accrual detector.
+pub struct PhiAccrual {
+    /// Recent inter-arrival times in milliseconds.
+    intervals: VecDeque<f64>,
+    /// Timestamp of the most recent heartbeat.
+    last_heartbeat: Option<Instant>,
+    /// Interval assumed before enough samples have accumulated (ms).
+    bootstrap_interval_ms: f64,
+}
+
+impl PhiAccrual {
+    /// Create a detector seeded with an expected heartbeat interval.
+    pub fn new(bootstrap_interval_ms: f64) -> Self {
+        Self {
+            intervals: VecDeque::with_capacity(MAX_SAMPLES),
+            last_heartbeat: None,
+            bootstrap_interval_ms: bootstrap_interval_ms.max(MIN_STD_DEV_MS),
+        }
+    }
+
+    /// Record a heartbeat, updating the interval window.
+    pub fn heartbeat(&mut self, now: Instant) {
+        if let Some(prev) = self.last_heartbeat {
+            let delta = now.duration_since(prev).as_secs_f64() * 1000.0;
+            if self.intervals.len() == MAX_SAMPLES {
+                self.intervals.pop_front();
+            }
+            self.intervals.push_back(delta);
+        }
+        self.last_heartbeat = Some(now);
+    }
+
+    /// Mean of the sampled intervals, falling back to the bootstrap value.
+    fn mean(&self) -> f64 {
+        if self.intervals.is_empty() {
+            return self.bootstrap_interval_ms;
+        }
+        self.intervals.iter().sum::<f64>() / self.intervals.len() as f64
+    }
+
+    /// Standard deviation of the sampled intervals, clamped to a floor.
+    fn std_dev(&self, mean: f64) -> f64 {
+        if self.intervals.len() < 2 {
+            return self.bootstrap_interval_ms.max(MIN_STD_DEV_MS);
+        }
+        let variance = self
+            .intervals
+            .iter()
+            .map(|x| (x - mean).powi(2))
+            .sum::<f64>()
+            / self.intervals.len() as f64;
+        variance.sqrt().max(MIN_STD_DEV_MS)
+    }
+
+    /// Current suspicion level. Higher means more likely failed.
+    pub fn phi(&self, now: Instant) -> f64 {
+        let Some(last) = self.last_heartbeat else {
+            return 0.0;
+        };
+        let elapsed = now.duration_since(last).as_secs_f64() * 1000.0;
+        let mean = self.mean();
+        let std_dev = self.std_dev(mean);
+
+        // Tail probability of a normal distribution; phi = -log10(P).
+        let p = 1.0 - normal_cdf(elapsed, mean, std_dev);
+        if p <= 0.0 {
+            return f64::INFINITY;
+        }
+        -p.log10()
+    }
+}
+
+/// CDF of a normal distribution via an erf approximation.
+fn normal_cdf(x: f64, mean: f64, std_dev: f64) -> f64 {
+    0.5 * (1.0 + erf((x - mean) / (std_dev * std::f64::consts::SQRT_2)))
+}
+
+/// Abramowitz & Stegun 7.1.26 approximation of the error function.
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let y = 1.0
+        - (((((1.061405429 * t - 1.453152027) * t) + 1.421413741) * t - 0.284496736) * t
+            + 0.254829592)
+            * t
+            * (-x * x).exp();
+    sign * y
+}
+
+/// Shared set of currently-suspected endpoint keys.
+///
+/// The [`crate::health::HealthChecker`] writes suspicions here and the
+/// [`crate::balancer::LoadBalancer`] reads them synchronously to exclude
+/// suspected endpoints from selection until phi recovers.
+#[derive(Debug, Default)]
+pub struct SuspicionSet {
+    suspected: RwLock<HashSet<String>>,
+}
+
+impl SuspicionSet {
+    /// Create an empty suspicion set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record or clear suspicion for an endpoint key.
+    pub fn set(&self, key: &str, suspected: bool) {
+        let mut guard = self.suspected.write().unwrap();
+        if suspected {
+            guard.insert(key.to_string());
+        } else {
+            guard.remove(key);
+        }
+    }
+
+    /// Whether an endpoint key is currently suspected.
+    pub fn is_suspected(&self, key: &str) -> bool {
+        self.suspected.read().unwrap().contains(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_phi_low_for_regular_heartbeats() {
+        let mut d = PhiAccrual::new(100.0);
+        let start = Instant::now();
+        for i in 0..10 {
+            d.heartbeat(start + Duration::from_millis(100 * i));
+        }
+        // Checked right at the expected next heartbeat: low suspicion.
+        let phi = d.phi(start + Duration::from_millis(100 * 10));
+        assert!(phi < 1.0, "phi was {}", phi);
+    }
+
+    #[test]
+    fn test_phi_rises_after_silence() {
+        let mut d = PhiAccrual::new(100.0);
+        let start = Instant::now();
+        for i in 0..10 {
+            d.heartbeat(start + Duration::from_millis(100 * i));
+        }
+        // A long silence relative to the ~100ms rhythm.
+        let phi = d.phi(start + Duration::from_millis(100 * 9 + 2000));
+        assert!(phi > DEFAULT_PHI_THRESHOLD, "phi was {}", phi);
+    }
+
+    #[test]
+    fn test_suspicion_set() {
+        let set = SuspicionSet::new();
+        assert!(!set.is_suspected("a:1"));
+        set.set("a:1", true);
+        assert!(set.is_suspected("a:1"));
+        set.set("a:1", false);
+        assert!(!set.is_suspected("a:1"));
+    }
+}
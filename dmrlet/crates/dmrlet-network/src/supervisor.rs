@@ -0,0 +1,165 @@
+//! Supervised health checking with backoff and restart escalation
+//!
+//! Wraps a worker's probe loop with an exponential-backoff retry policy using
+//! full jitter to avoid thundering-herd restarts. After a configurable number
+//! of consecutive failed probes the worker is marked [`WorkerStatus::Error`]
+//! and restarted (stop then start) through a [`WorkerControl`] implementation.
+
+use crate::health::HealthChecker;
+use dmrlet_core::{DmrletResult, Worker, WorkerStatus};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Restart policy applied when a worker's probes fail.
+#[derive(Debug, Clone)]
+pub struct BackoffPolicy {
+    /// Base delay for the first backoff step.
+    pub base_delay: Duration,
+    /// Upper bound on a single backoff step.
+    pub max_delay: Duration,
+    /// Consecutive failures tolerated before restarting.
+    pub count: u32,
+    /// Whether to apply full jitter to the computed delay.
+    pub jitter: bool,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            count: 3,
+            jitter: true,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Compute the delay for a given attempt number (0-based).
+    ///
+    /// The delay is `base_delay * 2^attempt` capped at `max_delay`; with jitter
+    /// enabled the returned value is sampled uniformly from `[0, delay]`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt);
+        let raw = self.base_delay.saturating_mul(factor.min(u32::MAX as u64) as u32);
+        let capped = raw.min(self.max_delay);
+
+        if self.jitter {
+            Duration::from_secs_f64(capped.as_secs_f64() * rand::random::<f64>())
+        } else {
+            capped
+        }
+    }
+}
+
+/// Lifecycle control for a supervised worker.
+///
+/// Implemented by the runtime layer (e.g. `ProcessRuntime`) so the supervisor
+/// can restart a worker without depending on a specific runtime crate.
+#[async_trait::async_trait]
+pub trait WorkerControl: Send + Sync {
+    /// Stop the worker's process.
+    async fn stop(&self, worker: &Worker) -> DmrletResult<()>;
+    /// Start the worker's process.
+    async fn start(&self, worker: &mut Worker) -> DmrletResult<()>;
+}
+
+/// Supervises a single worker's probe loop.
+pub struct HealthSupervisor {
+    checker: HealthChecker,
+    policy: BackoffPolicy,
+}
+
+impl HealthSupervisor {
+    /// Create a supervisor from a health checker and backoff policy.
+    pub fn new(checker: HealthChecker, policy: BackoffPolicy) -> Self {
+        Self { checker, policy }
+    }
+
+    /// Run the supervised probe loop until the worker is terminated.
+    ///
+    /// On each probe failure the supervisor sleeps for a backoff interval and
+    /// retries; after `policy.count` consecutive failures it marks the worker
+    /// `Error` and restarts it. The backoff resets on the first success.
+    pub async fn supervise<C: WorkerControl>(&self, worker: &mut Worker, control: &C) {
+        let mut failures: u32 = 0;
+
+        loop {
+            if matches!(
+                worker.status,
+                WorkerStatus::Terminating | WorkerStatus::Terminated
+            ) {
+                return;
+            }
+
+            if self.checker.check(&worker.endpoint).await {
+                if failures > 0 {
+                    info!(worker_id = %worker.id, "Worker recovered, resetting backoff");
+                }
+                failures = 0;
+                if worker.status != WorkerStatus::Running {
+                    worker.status = WorkerStatus::Running;
+                }
+                tokio::time::sleep(self.checker.timeout()).await;
+                continue;
+            }
+
+            failures += 1;
+            warn!(
+                worker_id = %worker.id,
+                failures,
+                threshold = self.policy.count,
+                "Worker probe failed"
+            );
+
+            if failures >= self.policy.count {
+                worker.status = WorkerStatus::Error;
+                info!(worker_id = %worker.id, "Restarting worker after repeated failures");
+                let _ = control.stop(worker).await;
+                match control.start(worker).await {
+                    Ok(()) => info!(worker_id = %worker.id, "Worker restarted"),
+                    Err(e) => warn!(worker_id = %worker.id, error = %e, "Restart failed"),
+                }
+                failures = 0;
+                continue;
+            }
+
+            let delay = self.policy.delay(failures - 1);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = BackoffPolicy {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+            count: 5,
+            jitter: false,
+        };
+        assert_eq!(policy.delay(0), Duration::from_secs(1));
+        assert_eq!(policy.delay(1), Duration::from_secs(2));
+        assert_eq!(policy.delay(2), Duration::from_secs(4));
+        // Capped at max_delay.
+        assert_eq!(policy.delay(20), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_full_jitter_within_bounds() {
+        let policy = BackoffPolicy {
+            base_delay: Duration::from_secs(4),
+            max_delay: Duration::from_secs(60),
+            count: 3,
+            jitter: true,
+        };
+        for _ in 0..100 {
+            let d = policy.delay(1); // computed delay is 8s
+            assert!(d <= Duration::from_secs(8));
+        }
+    }
+}
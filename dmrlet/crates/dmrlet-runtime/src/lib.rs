@@ -4,8 +4,10 @@
 //! - Process-based runtime for macOS/Windows
 //! - Container-based runtime for Linux (containerd)
 
+pub mod lifecycle;
 pub mod process;
 pub mod traits;
 
+pub use lifecycle::{TransitionLayer, WorkerLifecycle, WorkerState};
 pub use process::ProcessRuntime;
 pub use traits::Runtime;
@@ -0,0 +1,253 @@
+//! Worker lifecycle state machine and transition tracing
+//!
+//! The runtime drives each worker through an explicit state machine and emits
+//! a structured tracing event per transition, keyed by worker and deployment
+//! id, so the daemon records exactly when and why a worker changed state.
+//!
+//! A worker only reaches [`WorkerState::Running`] after a health check
+//! confirms its endpoint; a worker that fails during startup moves to
+//! [`WorkerState::Crashed`] without ever entering `Running`, which lets the
+//! runtime suppress a stop request for a process that never came up.
+
+use std::fmt;
+use std::time::SystemTime;
+use tracing::info;
+use uuid::Uuid;
+
+/// Explicit worker lifecycle states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerState {
+    /// Scheduled but not yet launched.
+    Queued,
+    /// Process spawned, awaiting health confirmation.
+    Starting,
+    /// Health check passed; serving traffic.
+    Running,
+    /// Graceful shutdown requested.
+    Stopping,
+    /// Fully stopped.
+    Stopped,
+    /// Failed for a non-startup reason while running.
+    Error,
+    /// Exited or failed during startup before reaching `Running`.
+    Crashed,
+}
+
+impl WorkerState {
+    /// Whether this state represents a worker that successfully reached
+    /// `Running` at some point and therefore needs a stop request to tear
+    /// down. A worker that only ever `Crashed` during startup does not.
+    pub fn needs_stop(&self) -> bool {
+        matches!(self, WorkerState::Running | WorkerState::Stopping | WorkerState::Error)
+    }
+
+    /// Validate a proposed transition from `self` to `next`.
+    pub fn can_transition_to(&self, next: WorkerState) -> bool {
+        use WorkerState::*;
+        matches!(
+            (self, next),
+            (Queued, Starting)
+                | (Starting, Running)
+                | (Starting, Crashed)
+                | (Running, Stopping)
+                | (Running, Error)
+                | (Stopping, Stopped)
+                | (Error, Stopping)
+                | (Error, Stopped)
+        )
+    }
+}
+
+impl fmt::Display for WorkerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            WorkerState::Queued => "Queued",
+            WorkerState::Starting => "Starting",
+            WorkerState::Running => "Running",
+            WorkerState::Stopping => "Stopping",
+            WorkerState::Stopped => "Stopped",
+            WorkerState::Error => "Error",
+            WorkerState::Crashed => "Crashed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A recorded state transition.
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub from: WorkerState,
+    pub to: WorkerState,
+    pub reason: String,
+    pub at: SystemTime,
+}
+
+/// Tracks a single worker's lifecycle and emits a tracing event per change.
+pub struct WorkerLifecycle {
+    worker_id: Uuid,
+    deployment_id: Uuid,
+    state: WorkerState,
+    history: Vec<Transition>,
+}
+
+impl WorkerLifecycle {
+    /// Create a lifecycle starting in `Queued`.
+    pub fn new(worker_id: Uuid, deployment_id: Uuid) -> Self {
+        Self {
+            worker_id,
+            deployment_id,
+            state: WorkerState::Queued,
+            history: Vec::new(),
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> WorkerState {
+        self.state
+    }
+
+    /// Recorded transition history.
+    pub fn history(&self) -> &[Transition] {
+        &self.history
+    }
+
+    /// Attempt a transition, recording and tracing it on success.
+    ///
+    /// Returns `false` if the transition is not permitted from the current
+    /// state, leaving the lifecycle unchanged.
+    pub fn transition(&mut self, to: WorkerState, reason: impl Into<String>) -> bool {
+        if !self.state.can_transition_to(to) {
+            return false;
+        }
+        let from = self.state;
+        let reason = reason.into();
+
+        info!(
+            worker_id = %self.worker_id,
+            deployment_id = %self.deployment_id,
+            from = %from,
+            to = %to,
+            reason = %reason,
+            "Worker state transition"
+        );
+
+        self.history.push(Transition {
+            from,
+            to,
+            reason,
+            at: SystemTime::now(),
+        });
+        self.state = to;
+        true
+    }
+}
+
+/// An audited transition captured from the tracing stream.
+#[derive(Debug, Clone, Default)]
+pub struct AuditedTransition {
+    pub worker_id: String,
+    pub deployment_id: String,
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+}
+
+/// A tracing layer that records worker state transitions into a shared buffer,
+/// giving operators an auditable timeline of deployment rollouts.
+pub struct TransitionLayer {
+    log: std::sync::Arc<std::sync::Mutex<Vec<AuditedTransition>>>,
+}
+
+impl TransitionLayer {
+    /// Create a layer writing into the given shared log.
+    pub fn new(log: std::sync::Arc<std::sync::Mutex<Vec<AuditedTransition>>>) -> Self {
+        Self { log }
+    }
+}
+
+impl<S> tracing_subscriber::Layer<S> for TransitionLayer
+where
+    S: tracing::Subscriber,
+{
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = TransitionVisitor::default();
+        event.record(&mut visitor);
+        if visitor.is_transition {
+            if let Ok(mut log) = self.log.lock() {
+                log.push(visitor.into_transition());
+            }
+        }
+    }
+}
+
+/// Field visitor that extracts transition fields from an event.
+#[derive(Default)]
+struct TransitionVisitor {
+    is_transition: bool,
+    worker_id: String,
+    deployment_id: String,
+    from: String,
+    to: String,
+    reason: String,
+}
+
+impl TransitionVisitor {
+    fn into_transition(self) -> AuditedTransition {
+        AuditedTransition {
+            worker_id: self.worker_id,
+            deployment_id: self.deployment_id,
+            from: self.from,
+            to: self.to,
+            reason: self.reason,
+        }
+    }
+}
+
+impl tracing::field::Visit for TransitionVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        let rendered = format!("{:?}", value);
+        match field.name() {
+            "message" if rendered.contains("Worker state transition") => self.is_transition = true,
+            "worker_id" => self.worker_id = rendered,
+            "deployment_id" => self.deployment_id = rendered,
+            "from" => self.from = rendered,
+            "to" => self.to = rendered,
+            "reason" => self.reason = rendered,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_crash_does_not_need_stop() {
+        let mut lc = WorkerLifecycle::new(Uuid::new_v4(), Uuid::new_v4());
+        assert!(lc.transition(WorkerState::Starting, "spawned"));
+        assert!(lc.transition(WorkerState::Crashed, "exited immediately"));
+        assert!(!lc.state().needs_stop());
+    }
+
+    #[test]
+    fn test_running_needs_stop() {
+        let mut lc = WorkerLifecycle::new(Uuid::new_v4(), Uuid::new_v4());
+        lc.transition(WorkerState::Starting, "spawned");
+        lc.transition(WorkerState::Running, "health check passed");
+        assert!(lc.state().needs_stop());
+        assert_eq!(lc.history().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_transition_rejected() {
+        let mut lc = WorkerLifecycle::new(Uuid::new_v4(), Uuid::new_v4());
+        // Cannot jump straight from Queued to Running.
+        assert!(!lc.transition(WorkerState::Running, "skip"));
+        assert_eq!(lc.state(), WorkerState::Queued);
+    }
+}
@@ -123,6 +123,18 @@ impl Runtime for ProcessRuntime {
     }
 
     async fn stop_worker(&self, worker: &Worker) -> DmrletResult<()> {
+        // A worker that failed during startup never reached Running, so its
+        // process is already gone — suppress the stop request rather than
+        // signalling a pid that may have been recycled by the OS.
+        if matches!(worker.status, WorkerStatus::Pending | WorkerStatus::Starting) {
+            debug!(
+                worker_id = %worker.id,
+                status = %worker.status,
+                "Suppressing stop for worker that never reached Running"
+            );
+            return Ok(());
+        }
+
         if let Some(pid) = worker.pid {
             info!(
                 worker_id = %worker.id,
@@ -0,0 +1,187 @@
+//! Horizontal autoscaling driven by CPU, memory, and GPU utilization.
+//!
+//! Implements the standard Kubernetes-HPA replica formula per signal and
+//! combines them by taking the largest desired count, clamped to the
+//! deployment's `[min_replicas, max_replicas]`. A stabilization window
+//! suppresses flapping by requiring scale-down proposals to persist across
+//! several consecutive samples before they take effect.
+
+use dmrlet_core::{AutoscaleConfig, GpuInfo, Worker};
+
+/// Observed utilization metrics for a single sampling tick.
+///
+/// Each field is the mean utilization (0-100) across the deployment's healthy
+/// replicas; `None` means the signal was not collected this tick.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ObservedMetrics {
+    /// Mean CPU utilization percentage.
+    pub cpu: Option<u32>,
+    /// Mean memory utilization percentage.
+    pub memory: Option<u32>,
+    /// Mean GPU utilization percentage.
+    pub gpu: Option<u32>,
+}
+
+/// Mean GPU utilization across the GPUs assigned to healthy workers.
+///
+/// Returns `None` when no healthy worker holds a GPU whose utilization is
+/// known, so the caller can simply omit the GPU signal that tick.
+pub fn mean_gpu_utilization(workers: &[Worker], gpu_info: &GpuInfo) -> Option<u32> {
+    let mut sum = 0u64;
+    let mut count = 0u64;
+    for worker in workers.iter().filter(|w| w.is_healthy()) {
+        for gpu_id in &worker.gpu_ids {
+            if let Some(util) = gpu_info
+                .devices
+                .iter()
+                .find(|d| d.index == *gpu_id)
+                .and_then(|d| d.utilization)
+            {
+                sum += util as u64;
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        None
+    } else {
+        Some((sum / count) as u32)
+    }
+}
+
+/// Autoscaler for a single deployment.
+pub struct Autoscaler {
+    config: AutoscaleConfig,
+    /// Number of consecutive samples that must agree before scaling down.
+    stabilization_window: u32,
+    /// How many consecutive ticks have proposed the pending scale-down target.
+    scale_down_streak: u32,
+    /// The lowest target seen during the current scale-down streak.
+    pending_target: Option<u32>,
+}
+
+impl Autoscaler {
+    /// Create an autoscaler with the given config and stabilization window.
+    pub fn new(config: AutoscaleConfig, stabilization_window: u32) -> Self {
+        Self {
+            config,
+            stabilization_window,
+            scale_down_streak: 0,
+            pending_target: None,
+        }
+    }
+
+    /// Desired replicas for one signal via the HPA formula, or `None` when the
+    /// signal is not configured or not observed.
+    fn desired_for(current_ready: u32, observed: Option<u32>, target: Option<u32>) -> Option<u32> {
+        let (observed, target) = (observed?, target?);
+        if target == 0 {
+            return None;
+        }
+        let ratio = observed as f64 / target as f64;
+        Some((current_ready as f64 * ratio).ceil() as u32)
+    }
+
+    /// Compute the target replica count for this tick.
+    ///
+    /// Scale-up proposals apply immediately; scale-down proposals are held
+    /// until they persist across the stabilization window.
+    pub fn target_replicas(&mut self, current_ready: u32, observed: ObservedMetrics) -> u32 {
+        let desired = [
+            Self::desired_for(current_ready, observed.cpu, self.config.target_cpu_utilization),
+            Self::desired_for(
+                current_ready,
+                observed.memory,
+                self.config.target_memory_utilization,
+            ),
+            Self::desired_for(current_ready, observed.gpu, self.config.target_gpu_utilization),
+        ]
+        .into_iter()
+        .flatten()
+        .max();
+
+        // With no configured/observed signal, hold steady.
+        let Some(raw) = desired else {
+            self.scale_down_streak = 0;
+            self.pending_target = None;
+            return current_ready.clamp(self.config.min_replicas, self.config.max_replicas);
+        };
+
+        let clamped = raw.clamp(self.config.min_replicas, self.config.max_replicas);
+
+        if clamped >= current_ready {
+            // Scale up (or hold): act immediately and clear any pending drain.
+            self.scale_down_streak = 0;
+            self.pending_target = None;
+            return clamped;
+        }
+
+        // Scale down: only honor it once it has persisted across the window.
+        match self.pending_target {
+            Some(prev) if clamped >= prev => {
+                self.scale_down_streak += 1;
+            }
+            _ => {
+                self.pending_target = Some(clamped);
+                self.scale_down_streak = 1;
+            }
+        }
+
+        if self.scale_down_streak >= self.stabilization_window {
+            let target = self.pending_target.take().unwrap_or(clamped);
+            self.scale_down_streak = 0;
+            target
+        } else {
+            current_ready
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AutoscaleConfig {
+        AutoscaleConfig {
+            enabled: true,
+            min_replicas: 1,
+            max_replicas: 10,
+            target_cpu_utilization: None,
+            target_memory_utilization: None,
+            target_gpu_utilization: Some(50),
+        }
+    }
+
+    #[test]
+    fn test_scale_up_immediate() {
+        let mut a = Autoscaler::new(config(), 3);
+        // 2 replicas at 100% vs 50% target => desired 4.
+        let target = a.target_replicas(2, ObservedMetrics { gpu: Some(100), ..Default::default() });
+        assert_eq!(target, 4);
+    }
+
+    #[test]
+    fn test_scale_down_requires_stabilization() {
+        let mut a = Autoscaler::new(config(), 3);
+        let obs = ObservedMetrics { gpu: Some(10), ..Default::default() };
+        // 4 replicas at 10% vs 50% => desired 1, but held until 3 samples agree.
+        assert_eq!(a.target_replicas(4, obs), 4);
+        assert_eq!(a.target_replicas(4, obs), 4);
+        assert_eq!(a.target_replicas(4, obs), 1);
+    }
+
+    #[test]
+    fn test_combine_takes_max() {
+        let mut cfg = config();
+        cfg.target_cpu_utilization = Some(50);
+        cfg.target_gpu_utilization = Some(50);
+        let mut a = Autoscaler::new(cfg, 3);
+        // CPU wants 2x (desired 4), GPU wants 1x (desired 2) => max is 4.
+        let obs = ObservedMetrics {
+            cpu: Some(100),
+            gpu: Some(50),
+            ..Default::default()
+        };
+        assert_eq!(a.target_replicas(2, obs), 4);
+    }
+}
@@ -1,15 +1,48 @@
 //! GPU allocator for tracking and assigning GPU devices
 
 use dmrlet_core::{DmrletError, DmrletResult, GpuDevice, GpuInfo};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Policy for honoring requests that exceed a device's physical free memory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OversubscribePolicy {
+    /// Never reserve more than a device's free memory.
+    Strict,
+    /// Allow reservations up to `ratio * memory_total` per device.
+    Overcommit { ratio: f64 },
+}
+
+impl Default for OversubscribePolicy {
+    fn default() -> Self {
+        OversubscribePolicy::Strict
+    }
+}
+
+/// A handle to reserved GPU memory, returned by [`GpuAllocator::allocate_memory`].
+#[derive(Debug, Clone)]
+pub struct GpuReservation {
+    /// Unique reservation identifier.
+    pub id: Uuid,
+    /// Physical device the memory was reserved on.
+    pub device_index: u32,
+    /// Number of bytes reserved.
+    pub reserved_bytes: u64,
+}
 
 /// GPU allocator that tracks GPU device assignments
 pub struct GpuAllocator {
     /// Available GPU devices
     devices: Vec<GpuDevice>,
-    /// Set of allocated GPU indices
+    /// Set of allocated GPU indices (whole-device allocation)
     allocated: HashSet<u32>,
+    /// Bytes reserved per device index (fractional allocation)
+    reserved: HashMap<u32, u64>,
+    /// Active reservations by id
+    reservations: HashMap<Uuid, GpuReservation>,
+    /// Over-subscription policy
+    policy: OversubscribePolicy,
 }
 
 impl GpuAllocator {
@@ -18,6 +51,9 @@ impl GpuAllocator {
         Self {
             devices: gpu_info.devices,
             allocated: HashSet::new(),
+            reserved: HashMap::new(),
+            reservations: HashMap::new(),
+            policy: OversubscribePolicy::default(),
         }
     }
 
@@ -26,6 +62,161 @@ impl GpuAllocator {
         Self {
             devices: Vec::new(),
             allocated: HashSet::new(),
+            reserved: HashMap::new(),
+            reservations: HashMap::new(),
+            policy: OversubscribePolicy::default(),
+        }
+    }
+
+    /// Set the over-subscription policy.
+    pub fn with_policy(mut self, policy: OversubscribePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Budget of reservable bytes on a device under the current policy.
+    fn budget(&self, device: &GpuDevice) -> u64 {
+        match self.policy {
+            OversubscribePolicy::Strict => device.memory_free,
+            OversubscribePolicy::Overcommit { ratio } => {
+                (device.memory_total as f64 * ratio) as u64
+            }
+        }
+    }
+
+    /// Bytes still free for reservation on a device.
+    fn device_free(&self, device: &GpuDevice) -> u64 {
+        let used = self.reserved.get(&device.index).copied().unwrap_or(0);
+        self.budget(device).saturating_sub(used)
+    }
+
+    /// Reserve memory for a model using best-fit across devices.
+    ///
+    /// Scans devices for the smallest one whose remaining free memory is at
+    /// least `model_bytes` (minimizing fragmentation), decrements its free
+    /// counter, and returns a reservation handle. A zero-byte request is
+    /// rejected; when no single device fits but the cluster total would, a
+    /// `ResourceExhausted` error is returned rather than splitting one worker
+    /// across GPUs.
+    pub fn allocate_memory(&mut self, model_bytes: u64) -> DmrletResult<GpuReservation> {
+        if model_bytes == 0 {
+            return Err(DmrletError::Gpu("Cannot reserve zero bytes".to_string()));
+        }
+
+        // Best-fit: smallest sufficient device by remaining free memory.
+        let best = self
+            .devices
+            .iter()
+            .filter(|d| d.available && self.device_free(d) >= model_bytes)
+            .min_by_key(|d| self.device_free(d))
+            .map(|d| d.index);
+
+        let Some(index) = best else {
+            let total_free: u64 = self.devices.iter().map(|d| self.device_free(d)).sum();
+            if total_free >= model_bytes {
+                return Err(DmrletError::ResourceExhausted(format!(
+                    "No single GPU fits {} bytes (fragmented across devices)",
+                    model_bytes
+                )));
+            }
+            return Err(DmrletError::ResourceExhausted(format!(
+                "Insufficient GPU memory for {} bytes",
+                model_bytes
+            )));
+        };
+
+        Ok(self.record_reservation(index, model_bytes))
+    }
+
+    /// Reserve memory on a specific device, for callers (such as the
+    /// scheduler's placement strategies) that have already chosen which GPU
+    /// to use and only need the byte-level bookkeeping `allocate_memory`
+    /// provides. Unlike `allocate_memory`, this never searches for a device,
+    /// so it cannot disagree with the caller's choice.
+    pub fn reserve_memory_on(&mut self, device: u32, bytes: u64) -> DmrletResult<GpuReservation> {
+        if bytes == 0 {
+            return Err(DmrletError::Gpu("Cannot reserve zero bytes".to_string()));
+        }
+
+        let device_info = self
+            .devices
+            .iter()
+            .find(|d| d.index == device)
+            .ok_or_else(|| DmrletError::Gpu(format!("Unknown GPU device: {}", device)))?;
+
+        if !device_info.available || self.device_free(device_info) < bytes {
+            return Err(DmrletError::ResourceExhausted(format!(
+                "GPU {} does not have {} bytes free",
+                device, bytes
+            )));
+        }
+
+        Ok(self.record_reservation(device, bytes))
+    }
+
+    /// Record a reservation of `bytes` on `device`, assuming the caller has
+    /// already verified capacity.
+    fn record_reservation(&mut self, device: u32, bytes: u64) -> GpuReservation {
+        *self.reserved.entry(device).or_insert(0) += bytes;
+        let reservation = GpuReservation {
+            id: Uuid::new_v4(),
+            device_index: device,
+            reserved_bytes: bytes,
+        };
+        self.reservations.insert(reservation.id, reservation.clone());
+
+        info!(
+            device,
+            bytes,
+            reservation = %reservation.id,
+            "Reserved GPU memory"
+        );
+        #[cfg(feature = "metrics")]
+        self.emit_metrics();
+        reservation
+    }
+
+    /// Release a memory reservation, returning exactly the reserved bytes.
+    pub fn release_memory(&mut self, reservation: &GpuReservation) {
+        if self.reservations.remove(&reservation.id).is_some() {
+            if let Some(used) = self.reserved.get_mut(&reservation.device_index) {
+                *used = used.saturating_sub(reservation.reserved_bytes);
+            }
+            debug!(
+                device = reservation.device_index,
+                bytes = reservation.reserved_bytes,
+                "Released GPU memory"
+            );
+        }
+        #[cfg(feature = "metrics")]
+        self.emit_metrics();
+    }
+
+    /// Release a memory reservation by id, looking it up internally. A no-op
+    /// if the reservation is unknown (already released).
+    pub fn release_memory_by_id(&mut self, id: Uuid) {
+        if let Some(reservation) = self.reservations.get(&id).cloned() {
+            self.release_memory(&reservation);
+        }
+    }
+
+    /// Publish the allocator's current utilization as gauges.
+    #[cfg(feature = "metrics")]
+    fn emit_metrics(&self) {
+        dmrlet_core::metrics::gauge_set("dmrlet_gpus_total", &[], self.total_count() as f64);
+        dmrlet_core::metrics::gauge_set(
+            "dmrlet_gpus_available",
+            &[],
+            self.available_count() as f64,
+        );
+        for device in &self.devices {
+            let reserved = self.reserved.get(&device.index).copied().unwrap_or(0);
+            let index = device.index.to_string();
+            dmrlet_core::metrics::gauge_set(
+                "dmrlet_gpu_reserved_bytes",
+                &[("device", index.as_str())],
+                reserved as f64,
+            );
         }
     }
 
@@ -76,9 +267,49 @@ impl GpuAllocator {
             "Allocated GPUs"
         );
 
+        #[cfg(feature = "metrics")]
+        self.emit_metrics();
         Ok(allocated_indices)
     }
 
+    /// Allocate exactly the given GPU indices, as already chosen by a
+    /// placement strategy.
+    ///
+    /// Unlike `allocate`, which picks the first `count` available devices
+    /// itself, this reserves the specific set the caller asks for, so the
+    /// allocator's bookkeeping always agrees with whatever GPUs the worker
+    /// actually ends up using. Fails without reserving anything if any index
+    /// is unknown, unavailable, or already allocated.
+    pub fn allocate_specific(&mut self, indices: &[u32]) -> DmrletResult<()> {
+        if indices.is_empty() {
+            return Ok(());
+        }
+
+        for &idx in indices {
+            let device = self
+                .devices
+                .iter()
+                .find(|d| d.index == idx)
+                .ok_or_else(|| DmrletError::Gpu(format!("Unknown GPU device: {}", idx)))?;
+            if !device.available || self.allocated.contains(&idx) {
+                return Err(DmrletError::ResourceExhausted(format!(
+                    "GPU {} is not available for allocation",
+                    idx
+                )));
+            }
+        }
+
+        for &idx in indices {
+            self.allocated.insert(idx);
+        }
+
+        info!(gpus = ?indices, "Allocated GPUs");
+
+        #[cfg(feature = "metrics")]
+        self.emit_metrics();
+        Ok(())
+    }
+
     /// Release previously allocated GPUs
     pub fn release(&mut self, indices: &[u32]) {
         for idx in indices {
@@ -86,6 +317,8 @@ impl GpuAllocator {
                 debug!(gpu = idx, "Released GPU");
             }
         }
+        #[cfg(feature = "metrics")]
+        self.emit_metrics();
     }
 
     /// Get information about all GPUs
@@ -95,6 +328,8 @@ impl GpuAllocator {
             .map(|d| GpuDeviceStatus {
                 device: d.clone(),
                 allocated: self.allocated.contains(&d.index),
+                reserved_bytes: self.reserved.get(&d.index).copied().unwrap_or(0),
+                free_bytes: self.device_free(d),
             })
             .collect()
     }
@@ -105,8 +340,12 @@ impl GpuAllocator {
 pub struct GpuDeviceStatus {
     /// Device information
     pub device: GpuDevice,
-    /// Whether this device is currently allocated
+    /// Whether this device is currently allocated (whole-device)
     pub allocated: bool,
+    /// Bytes currently reserved on this device (fractional)
+    pub reserved_bytes: u64,
+    /// Bytes still available for reservation under the active policy
+    pub free_bytes: u64,
 }
 
 #[cfg(test)]
@@ -124,6 +363,11 @@ mod tests {
                 vendor: GpuVendor::Nvidia,
                 available: true,
                 utilization: Some(0),
+                pci: None,
+                temperature_c: None,
+                power_usage_mw: None,
+                power_limit_mw: None,
+                processes: Vec::new(),
             })
             .collect();
 
@@ -173,4 +417,92 @@ mod tests {
         let result = allocator.allocate(4);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_allocate_memory_best_fit() {
+        let gpu_info = create_test_gpu_info(2);
+        let mut allocator = GpuAllocator::new(gpu_info);
+
+        let gib = 1024u64 * 1024 * 1024;
+        let res = allocator.allocate_memory(4 * gib).unwrap();
+        assert_eq!(res.reserved_bytes, 4 * gib);
+
+        // Reserved bytes are reflected in per-device status.
+        let status = allocator.get_gpu_info();
+        let reserved_total: u64 = status.iter().map(|s| s.reserved_bytes).sum();
+        assert_eq!(reserved_total, 4 * gib);
+
+        allocator.release_memory(&res);
+        let reserved_total: u64 = allocator
+            .get_gpu_info()
+            .iter()
+            .map(|s| s.reserved_bytes)
+            .sum();
+        assert_eq!(reserved_total, 0);
+    }
+
+    #[test]
+    fn test_allocate_memory_rejects_zero() {
+        let mut allocator = GpuAllocator::new(create_test_gpu_info(1));
+        assert!(allocator.allocate_memory(0).is_err());
+    }
+
+    #[test]
+    fn test_allocate_specific_reserves_exact_indices() {
+        let gpu_info = create_test_gpu_info(4);
+        let mut allocator = GpuAllocator::new(gpu_info);
+
+        allocator.allocate_specific(&[1, 3]).unwrap();
+        assert_eq!(allocator.available_count(), 2);
+
+        allocator.release(&[1, 3]);
+        assert_eq!(allocator.available_count(), 4);
+    }
+
+    #[test]
+    fn test_allocate_specific_rejects_already_allocated() {
+        let gpu_info = create_test_gpu_info(2);
+        let mut allocator = GpuAllocator::new(gpu_info);
+
+        allocator.allocate_specific(&[0]).unwrap();
+        let result = allocator.allocate_specific(&[0, 1]);
+        assert!(result.is_err());
+        // The failed call must not have partially reserved GPU 1.
+        assert_eq!(allocator.available_count(), 1);
+    }
+
+    #[test]
+    fn test_reserve_memory_on_specific_device() {
+        let mut allocator = GpuAllocator::new(create_test_gpu_info(2));
+        let gib = 1024u64 * 1024 * 1024;
+
+        let reservation = allocator.reserve_memory_on(1, 4 * gib).unwrap();
+        assert_eq!(reservation.device_index, 1);
+
+        let status = allocator.get_gpu_info();
+        assert_eq!(status[1].reserved_bytes, 4 * gib);
+        assert_eq!(status[0].reserved_bytes, 0);
+
+        allocator.release_memory_by_id(reservation.id);
+        assert_eq!(allocator.get_gpu_info()[1].reserved_bytes, 0);
+    }
+
+    #[test]
+    fn test_reserve_memory_on_rejects_insufficient_device() {
+        let mut allocator = GpuAllocator::new(create_test_gpu_info(1));
+        let gib = 1024u64 * 1024 * 1024;
+        assert!(allocator.reserve_memory_on(0, 32 * gib).is_err());
+    }
+
+    #[test]
+    fn test_allocate_memory_fragmented() {
+        // Two 16GiB devices; a 24GiB model fits in neither but fits in total.
+        let mut allocator = GpuAllocator::new(create_test_gpu_info(2));
+        let gib = 1024u64 * 1024 * 1024;
+        let result = allocator.allocate_memory(24 * gib);
+        assert!(matches!(
+            result,
+            Err(dmrlet_core::DmrletError::ResourceExhausted(_))
+        ));
+    }
 }
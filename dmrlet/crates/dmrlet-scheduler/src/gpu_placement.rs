@@ -0,0 +1,60 @@
+//! Memory-quantity parsing shared by GPU placement.
+//!
+//! The actual GPU bin-packing lives in [`crate::placement`], whose strategies
+//! operate on live [`GpuCandidate`](crate::placement::GpuCandidate) snapshots
+//! from the scheduler's allocators; this module only parses the Kubernetes-
+//! style memory quantities those strategies divide across replicas.
+
+use dmrlet_core::{DmrletError, DmrletResult};
+
+/// Parse a Kubernetes-style memory quantity (e.g. `"16Gi"`, `"512Mi"`, `"2G"`)
+/// into a byte count.
+///
+/// Binary suffixes (`Ki`/`Mi`/`Gi`/`Ti`) use powers of 1024; decimal suffixes
+/// (`K`/`M`/`G`/`T`) use powers of 1000. A bare number is interpreted as bytes.
+pub fn parse_memory(quantity: &str) -> DmrletResult<u64> {
+    let s = quantity.trim();
+    if s.is_empty() {
+        return Err(DmrletError::Config("empty memory quantity".to_string()));
+    }
+
+    let (number, multiplier) = if let Some(n) = s.strip_suffix("Ki") {
+        (n, 1024u64)
+    } else if let Some(n) = s.strip_suffix("Mi") {
+        (n, 1024 * 1024)
+    } else if let Some(n) = s.strip_suffix("Gi") {
+        (n, 1024 * 1024 * 1024)
+    } else if let Some(n) = s.strip_suffix("Ti") {
+        (n, 1024u64 * 1024 * 1024 * 1024)
+    } else if let Some(n) = s.strip_suffix('K') {
+        (n, 1_000)
+    } else if let Some(n) = s.strip_suffix('M') {
+        (n, 1_000_000)
+    } else if let Some(n) = s.strip_suffix('G') {
+        (n, 1_000_000_000)
+    } else if let Some(n) = s.strip_suffix('T') {
+        (n, 1_000_000_000_000)
+    } else {
+        (s, 1)
+    };
+
+    number
+        .trim()
+        .parse::<u64>()
+        .map(|n| n * multiplier)
+        .map_err(|_| DmrletError::Config(format!("invalid memory quantity: {}", quantity)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("16Gi").unwrap(), 16 * 1024 * 1024 * 1024);
+        assert_eq!(parse_memory("512Mi").unwrap(), 512 * 1024 * 1024);
+        assert_eq!(parse_memory("2G").unwrap(), 2_000_000_000);
+        assert_eq!(parse_memory("1024").unwrap(), 1024);
+        assert!(parse_memory("bogus").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Cluster topology and replica placement layout
+//!
+//! Each node in the cluster carries topology metadata — a zone, a capacity
+//! weight, and a set of free-form `key=value` tags — plus a `draining` flag.
+//! The [`ClusterLayout`] tracks these descriptors behind a monotonic version
+//! counter and computes placements that spread a deployment's replicas across
+//! distinct zones for fault tolerance, weight candidate nodes by remaining
+//! capacity, and honor tag-based constraints.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Topology metadata for a single cluster node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeDescriptor {
+    /// Stable node identifier.
+    pub id: String,
+    /// Reachable host address (IP or hostname, no port) of the node's worker host.
+    #[serde(default)]
+    pub address: String,
+    /// Failure domain (rack, availability zone, ...).
+    pub zone: String,
+    /// Relative capacity weight; higher takes proportionally more replicas.
+    pub capacity: u32,
+    /// Free-form `key=value` tags used for constraint matching.
+    pub tags: Vec<String>,
+    /// When set, no new replicas are placed here and existing ones migrate away.
+    pub draining: bool,
+    /// Timestamp of the node's most recent registration/heartbeat.
+    #[serde(default)]
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+impl NodeDescriptor {
+    /// Create a schedulable node descriptor.
+    pub fn new(id: impl Into<String>, zone: impl Into<String>, capacity: u32) -> Self {
+        Self {
+            id: id.into(),
+            address: String::new(),
+            zone: zone.into(),
+            capacity: capacity.max(1),
+            tags: Vec::new(),
+            draining: false,
+            last_seen: None,
+        }
+    }
+
+    /// Set the node's reachable host address.
+    pub fn with_address(mut self, address: impl Into<String>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Whether this node carries every required tag.
+    fn satisfies(&self, required: &[String]) -> bool {
+        required.iter().all(|r| self.tags.iter().any(|t| t == r))
+    }
+}
+
+/// Versioned view of cluster topology used for placement.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterLayout {
+    version: u64,
+    nodes: HashMap<String, NodeDescriptor>,
+}
+
+impl ClusterLayout {
+    /// Create an empty layout at version 0.
+    pub fn new() -> Self {
+        Self {
+            version: 0,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Current layout version.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Whether any nodes are registered.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Insert or replace a node descriptor, bumping the layout version.
+    pub fn upsert_node(&mut self, node: NodeDescriptor) {
+        self.nodes.insert(node.id.clone(), node);
+        self.version += 1;
+    }
+
+    /// Mark a node draining. Returns `false` if the node is unknown.
+    pub fn set_draining(&mut self, id: &str, draining: bool) -> bool {
+        match self.nodes.get_mut(id) {
+            Some(node) => {
+                node.draining = draining;
+                self.version += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Schedulable nodes satisfying `required_tags`, excluding draining ones.
+    fn candidates(&self, required_tags: &[String]) -> Vec<&NodeDescriptor> {
+        self.nodes
+            .values()
+            .filter(|n| !n.draining && n.satisfies(required_tags))
+            .collect()
+    }
+
+    /// Place `count` replicas across candidate nodes.
+    ///
+    /// Replicas are spread across distinct zones first (so a zone failure takes
+    /// out as few replicas as possible), and within that spread each replica is
+    /// assigned to the node whose load relative to its capacity weight is
+    /// lowest. Returns one node id per replica, or fewer if the constraints
+    /// leave no eligible node at all.
+    pub fn place_replicas(&self, count: u32, required_tags: &[String]) -> Vec<String> {
+        let candidates = self.candidates(required_tags);
+        if candidates.is_empty() {
+            return Vec::new();
+        }
+
+        // Running replica count per node.
+        let mut load: HashMap<&str, u32> = HashMap::new();
+        let mut placement = Vec::with_capacity(count as usize);
+
+        for _ in 0..count {
+            // Zone spread: prefer a zone holding the fewest replicas so far.
+            let mut zone_load: HashMap<&str, u32> = HashMap::new();
+            for n in &candidates {
+                *zone_load.entry(n.zone.as_str()).or_insert(0) +=
+                    load.get(n.id.as_str()).copied().unwrap_or(0);
+            }
+            let target_zone = zone_load
+                .iter()
+                .min_by_key(|(_, l)| **l)
+                .map(|(z, _)| *z)
+                .unwrap();
+
+            // Capacity weighting: lowest load-to-capacity ratio within the zone.
+            let chosen = candidates
+                .iter()
+                .filter(|n| n.zone == target_zone)
+                .min_by(|a, b| {
+                    let ra = ratio(load.get(a.id.as_str()).copied().unwrap_or(0), a.capacity);
+                    let rb = ratio(load.get(b.id.as_str()).copied().unwrap_or(0), b.capacity);
+                    ra.partial_cmp(&rb).unwrap()
+                })
+                .unwrap();
+
+            *load.entry(chosen.id.as_str()).or_insert(0) += 1;
+            placement.push(chosen.id.clone());
+        }
+
+        placement
+    }
+}
+
+/// Load-to-capacity ratio used for capacity-weighted selection.
+fn ratio(load: u32, capacity: u32) -> f64 {
+    load as f64 / capacity.max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> ClusterLayout {
+        let mut l = ClusterLayout::new();
+        l.upsert_node(NodeDescriptor::new("a", "zone-1", 1));
+        l.upsert_node(NodeDescriptor::new("b", "zone-2", 1));
+        l
+    }
+
+    #[test]
+    fn test_version_bumps_on_change() {
+        let mut l = ClusterLayout::new();
+        assert_eq!(l.version(), 0);
+        l.upsert_node(NodeDescriptor::new("a", "zone-1", 1));
+        assert_eq!(l.version(), 1);
+        l.set_draining("a", true);
+        assert_eq!(l.version(), 2);
+    }
+
+    #[test]
+    fn test_spreads_across_zones() {
+        let l = layout();
+        let placement = l.place_replicas(2, &[]);
+        assert_eq!(placement.len(), 2);
+        // One replica in each zone.
+        assert!(placement.contains(&"a".to_string()));
+        assert!(placement.contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn test_capacity_weighting() {
+        let mut l = ClusterLayout::new();
+        l.upsert_node(NodeDescriptor::new("big", "zone-1", 4));
+        l.upsert_node(NodeDescriptor::new("small", "zone-1", 1));
+        let placement = l.place_replicas(5, &[]);
+        let big = placement.iter().filter(|id| *id == "big").count();
+        let small = placement.iter().filter(|id| *id == "small").count();
+        assert!(big > small, "higher-capacity node should take more replicas");
+    }
+
+    #[test]
+    fn test_tag_constraint() {
+        let mut l = ClusterLayout::new();
+        let mut nvidia = NodeDescriptor::new("gpu", "zone-1", 1);
+        nvidia.tags.push("gpu=nvidia".to_string());
+        l.upsert_node(nvidia);
+        l.upsert_node(NodeDescriptor::new("cpu", "zone-2", 1));
+
+        let placement = l.place_replicas(2, &["gpu=nvidia".to_string()]);
+        assert!(placement.iter().all(|id| id == "gpu"));
+    }
+
+    #[test]
+    fn test_draining_excluded() {
+        let mut l = layout();
+        l.set_draining("a", true);
+        let placement = l.place_replicas(3, &[]);
+        assert!(placement.iter().all(|id| id == "b"));
+    }
+}
@@ -5,10 +5,24 @@
 //! - Worker placement decisions
 //! - Resource management
 
+pub mod autoscaler;
 pub mod gpu_allocator;
+pub mod gpu_placement;
+pub mod layout;
 pub mod placement;
 pub mod scheduler;
+pub mod state;
 
+pub use autoscaler::{mean_gpu_utilization, Autoscaler, ObservedMetrics};
 pub use gpu_allocator::GpuAllocator;
-pub use placement::PlacementDecision;
-pub use scheduler::Scheduler;
+pub use gpu_placement::parse_memory;
+pub use layout::{ClusterLayout, NodeDescriptor};
+pub use placement::{
+    strategy_for, BinPackStrategy, DefaultPlacementStrategy, GpuCandidate, PlacementDecision,
+    PlacementStrategy, RendezvousStrategy, SpreadStrategy,
+};
+pub use scheduler::{Scheduler, DEFAULT_HEARTBEAT_TIMEOUT, DEFAULT_RECONCILE_INTERVAL};
+pub use state::{MemoryStateStore, SqlStateStore, StateStore};
+
+// Re-export cache types surfaced through the scheduler's status API.
+pub use dmrlet_store::cache::{CacheStats, EvictionEvent, ScrubReport};
@@ -1,10 +1,17 @@
 //! Worker placement decisions
 
-use dmrlet_core::DeploymentSpec;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use dmrlet_core::{DeploymentSpec, PlacementStrategyKind};
+
+use crate::gpu_placement::parse_memory;
 
 /// Placement decision for a worker
 #[derive(Debug, Clone)]
 pub struct PlacementDecision {
+    /// Cluster node the worker is placed on, if topology is configured
+    pub node_id: Option<String>,
     /// Assigned GPU indices
     pub gpu_ids: Vec<u32>,
     /// Assigned port number
@@ -13,19 +20,79 @@ pub struct PlacementDecision {
     pub worker_index: u32,
 }
 
+/// A GPU offered to a placement strategy, with its live free memory and the
+/// number of workers already co-located on it.
+#[derive(Debug, Clone)]
+pub struct GpuCandidate {
+    /// Device index
+    pub index: u32,
+    /// Bytes currently free for reservation on the device
+    pub free_memory: u64,
+    /// Workers already placed on the device
+    pub worker_load: u32,
+}
+
 /// Strategy for making placement decisions
 pub trait PlacementStrategy: Send + Sync {
-    /// Make a placement decision for a new worker
+    /// Make a placement decision for a new worker.
+    ///
+    /// `candidates` carries per-GPU free memory and current worker load so a
+    /// strategy can pack or spread; `available_ports` is the free port pool.
     fn place(
         &self,
         spec: &DeploymentSpec,
         existing_worker_count: u32,
-        available_gpus: &[u32],
+        candidates: &[GpuCandidate],
         available_ports: &[u16],
     ) -> Option<PlacementDecision>;
 }
 
-/// Default placement strategy
+/// Resolve the strategy implementation for a deployment's chosen kind.
+pub fn strategy_for(kind: PlacementStrategyKind) -> Box<dyn PlacementStrategy> {
+    match kind {
+        PlacementStrategyKind::BinPack => Box::new(BinPackStrategy),
+        PlacementStrategyKind::Spread => Box::new(SpreadStrategy),
+        PlacementStrategyKind::Rendezvous => Box::new(RendezvousStrategy),
+        PlacementStrategyKind::Default => Box::new(DefaultPlacementStrategy),
+    }
+}
+
+/// Required memory per selected GPU, dividing the deployment's total request
+/// evenly across the `gpu_count` devices it will be sharded over.
+pub(crate) fn per_gpu_requirement(spec: &DeploymentSpec, gpu_count: usize) -> u64 {
+    let total = spec
+        .resources
+        .memory
+        .as_deref()
+        .and_then(|m| parse_memory(m).ok())
+        .unwrap_or(0);
+    if gpu_count > 0 {
+        total.div_ceil(gpu_count as u64)
+    } else {
+        total
+    }
+}
+
+/// Select `gpu_count` GPUs that each satisfy `per_gpu`, ordered by `key`.
+///
+/// Returns `None` when fewer than `gpu_count` candidates fit, which the
+/// scheduler surfaces as `ResourceExhausted`.
+fn select_by<K: Ord>(
+    candidates: &[GpuCandidate],
+    gpu_count: usize,
+    per_gpu: u64,
+    key: impl Fn(&GpuCandidate) -> K,
+) -> Option<Vec<u32>> {
+    let mut fitting: Vec<&GpuCandidate> =
+        candidates.iter().filter(|c| c.free_memory >= per_gpu).collect();
+    if fitting.len() < gpu_count {
+        return None;
+    }
+    fitting.sort_by_key(|c| key(c));
+    Some(fitting.iter().take(gpu_count).map(|c| c.index).collect())
+}
+
+/// Default placement strategy: first-fit by GPU count, ignoring memory.
 pub struct DefaultPlacementStrategy;
 
 impl PlacementStrategy for DefaultPlacementStrategy {
@@ -33,26 +100,26 @@ impl PlacementStrategy for DefaultPlacementStrategy {
         &self,
         spec: &DeploymentSpec,
         existing_worker_count: u32,
-        available_gpus: &[u32],
+        candidates: &[GpuCandidate],
         available_ports: &[u16],
     ) -> Option<PlacementDecision> {
-        // Check if we have resources
         if available_ports.is_empty() {
             return None;
         }
 
         let gpu_count = spec.resources.gpu_count as usize;
-        if gpu_count > 0 && available_gpus.len() < gpu_count {
+        if gpu_count > 0 && candidates.len() < gpu_count {
             return None;
         }
 
-        let gpu_ids = if gpu_count > 0 {
-            available_gpus[..gpu_count].to_vec()
-        } else {
-            Vec::new()
-        };
+        let gpu_ids = candidates
+            .iter()
+            .take(gpu_count)
+            .map(|c| c.index)
+            .collect();
 
         Some(PlacementDecision {
+            node_id: None,
             gpu_ids,
             port: available_ports[0],
             worker_index: existing_worker_count,
@@ -60,10 +127,172 @@ impl PlacementStrategy for DefaultPlacementStrategy {
     }
 }
 
+/// Bin-pack strategy: consolidate onto the fullest GPUs that still fit, so
+/// whole GPUs stay free for large models.
+pub struct BinPackStrategy;
+
+impl PlacementStrategy for BinPackStrategy {
+    fn place(
+        &self,
+        spec: &DeploymentSpec,
+        existing_worker_count: u32,
+        candidates: &[GpuCandidate],
+        available_ports: &[u16],
+    ) -> Option<PlacementDecision> {
+        let port = *available_ports.first()?;
+        let gpu_count = spec.resources.gpu_count as usize;
+        if gpu_count == 0 {
+            return Some(PlacementDecision {
+                node_id: None,
+                gpu_ids: Vec::new(),
+                port,
+                worker_index: existing_worker_count,
+            });
+        }
+
+        let per_gpu = per_gpu_requirement(spec, gpu_count);
+        // Least free memory first (but still fitting), tie-break on heavier load.
+        let gpu_ids = select_by(candidates, gpu_count, per_gpu, |c| {
+            (c.free_memory, std::cmp::Reverse(c.worker_load))
+        })?;
+
+        Some(PlacementDecision {
+            node_id: None,
+            gpu_ids,
+            port,
+            worker_index: existing_worker_count,
+        })
+    }
+}
+
+/// Spread strategy: prefer the emptiest, least-loaded GPUs to minimize
+/// contention between co-located workers.
+pub struct SpreadStrategy;
+
+impl PlacementStrategy for SpreadStrategy {
+    fn place(
+        &self,
+        spec: &DeploymentSpec,
+        existing_worker_count: u32,
+        candidates: &[GpuCandidate],
+        available_ports: &[u16],
+    ) -> Option<PlacementDecision> {
+        let port = *available_ports.first()?;
+        let gpu_count = spec.resources.gpu_count as usize;
+        if gpu_count == 0 {
+            return Some(PlacementDecision {
+                node_id: None,
+                gpu_ids: Vec::new(),
+                port,
+                worker_index: existing_worker_count,
+            });
+        }
+
+        let per_gpu = per_gpu_requirement(spec, gpu_count);
+        // Fewest co-located workers first, then most free memory.
+        let gpu_ids = select_by(candidates, gpu_count, per_gpu, |c| {
+            (c.worker_load, std::cmp::Reverse(c.free_memory))
+        })?;
+
+        Some(PlacementDecision {
+            node_id: None,
+            gpu_ids,
+            port,
+            worker_index: existing_worker_count,
+        })
+    }
+}
+
+/// Weighted rendezvous (highest-random-weight) strategy: deterministically
+/// maps each `(model, replica)` pair onto a GPU via a weighted hash, so
+/// rescheduling keeps a model pinned to the same device(s) instead of
+/// triggering an avoidable re-download. Because the mapping only depends on
+/// the candidate set, adding or removing one GPU reshuffles just the slice
+/// of placements that hashed nearest to it rather than the whole fleet.
+pub struct RendezvousStrategy;
+
+impl PlacementStrategy for RendezvousStrategy {
+    fn place(
+        &self,
+        spec: &DeploymentSpec,
+        existing_worker_count: u32,
+        candidates: &[GpuCandidate],
+        available_ports: &[u16],
+    ) -> Option<PlacementDecision> {
+        let port = *available_ports.first()?;
+        let gpu_count = spec.resources.gpu_count as usize;
+        if gpu_count == 0 {
+            return Some(PlacementDecision {
+                node_id: None,
+                gpu_ids: Vec::new(),
+                port,
+                worker_index: existing_worker_count,
+            });
+        }
+
+        let per_gpu = per_gpu_requirement(spec, gpu_count);
+        let fitting: Vec<&GpuCandidate> =
+            candidates.iter().filter(|c| c.free_memory >= per_gpu).collect();
+        if fitting.len() < gpu_count {
+            return None;
+        }
+
+        let mut scored: Vec<(f64, u32)> = fitting
+            .iter()
+            .map(|c| (rendezvous_score(&spec.model, existing_worker_count, c), c.index))
+            .collect();
+        // Highest score wins; break ties on GPU index for determinism.
+        scored.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.1.cmp(&b.1))
+        });
+
+        let gpu_ids = scored.into_iter().take(gpu_count).map(|(_, index)| index).collect();
+
+        Some(PlacementDecision {
+            node_id: None,
+            gpu_ids,
+            port,
+            worker_index: existing_worker_count,
+        })
+    }
+}
+
+/// Weighted HRW score for `(model, replica)` against `gpu`:
+/// `weight(gpu) / -ln(hash(model, replica, gpu.index) / MAX)`, where `weight`
+/// is proportional to free memory. The hash is a deterministic function of
+/// the triple, so the same inputs always produce the same ranking.
+fn rendezvous_score(model: &str, replica: u32, gpu: &GpuCandidate) -> f64 {
+    let mut hasher = DefaultHasher::new();
+    model.hash(&mut hasher);
+    replica.hash(&mut hasher);
+    gpu.index.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    // Normalize into (0, 1] so `ln` never sees zero.
+    let normalized = (hash as f64 + 1.0) / (u64::MAX as f64 + 1.0);
+    let weight = (gpu.free_memory as f64).max(1.0);
+    weight / -normalized.ln()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn candidates(specs: &[(u32, u64, u32)]) -> Vec<GpuCandidate> {
+        specs
+            .iter()
+            .map(|&(index, free_memory, worker_load)| GpuCandidate {
+                index,
+                free_memory,
+                worker_load,
+            })
+            .collect()
+    }
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
     #[test]
     fn test_default_placement_no_gpus() {
         let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
@@ -83,7 +312,8 @@ mod tests {
         spec.resources.gpu_count = 2;
 
         let strategy = DefaultPlacementStrategy;
-        let decision = strategy.place(&spec, 0, &[0, 1, 2, 3], &[30000]);
+        let gpus = candidates(&[(0, GB, 0), (1, GB, 0), (2, GB, 0), (3, GB, 0)]);
+        let decision = strategy.place(&spec, 0, &gpus, &[30000]);
 
         assert!(decision.is_some());
         let d = decision.unwrap();
@@ -96,8 +326,91 @@ mod tests {
         spec.resources.gpu_count = 4;
 
         let strategy = DefaultPlacementStrategy;
-        let decision = strategy.place(&spec, 0, &[0, 1], &[30000]);
+        let gpus = candidates(&[(0, GB, 0), (1, GB, 0)]);
+        let decision = strategy.place(&spec, 0, &gpus, &[30000]);
 
         assert!(decision.is_none());
     }
+
+    #[test]
+    fn test_bin_pack_prefers_fullest_fitting_gpu() {
+        let mut spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("4Gi".to_string());
+
+        let gpus = candidates(&[(0, 16 * GB, 0), (1, 6 * GB, 0), (2, 2 * GB, 0)]);
+        let decision = BinPackStrategy.place(&spec, 0, &gpus, &[30000]).unwrap();
+        // GPU 1 is the least-free device that still fits 4Gi; GPU 2 is too small.
+        assert_eq!(decision.gpu_ids, vec![1]);
+    }
+
+    #[test]
+    fn test_spread_prefers_least_loaded_gpu() {
+        let mut spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("4Gi".to_string());
+
+        let gpus = candidates(&[(0, 8 * GB, 3), (1, 8 * GB, 0), (2, 8 * GB, 1)]);
+        let decision = SpreadStrategy.place(&spec, 0, &gpus, &[30000]).unwrap();
+        assert_eq!(decision.gpu_ids, vec![1]);
+    }
+
+    #[test]
+    fn test_memory_requirement_rejects_placement() {
+        let mut spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("32Gi".to_string());
+
+        let gpus = candidates(&[(0, 8 * GB, 0), (1, 16 * GB, 0)]);
+        assert!(BinPackStrategy.place(&spec, 0, &gpus, &[30000]).is_none());
+        assert!(SpreadStrategy.place(&spec, 0, &gpus, &[30000]).is_none());
+    }
+
+    #[test]
+    fn test_rendezvous_is_deterministic() {
+        let mut spec = DeploymentSpec::new("test".to_string(), "ai/llama3:8b".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("4Gi".to_string());
+
+        let gpus = candidates(&[(0, 16 * GB, 0), (1, 16 * GB, 0), (2, 16 * GB, 0)]);
+        let first = RendezvousStrategy.place(&spec, 0, &gpus, &[30000]).unwrap();
+        let second = RendezvousStrategy.place(&spec, 0, &gpus, &[30000]).unwrap();
+        assert_eq!(first.gpu_ids, second.gpu_ids);
+    }
+
+    #[test]
+    fn test_rendezvous_mostly_stable_when_a_gpu_is_removed() {
+        let mut spec = DeploymentSpec::new("test".to_string(), "ai/llama3:8b".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("4Gi".to_string());
+
+        // Place several independent replicas against the full GPU set...
+        let full = candidates(&[(0, 16 * GB, 0), (1, 16 * GB, 0), (2, 16 * GB, 0), (3, 16 * GB, 0)]);
+        let before: Vec<u32> = (0..8)
+            .map(|replica| RendezvousStrategy.place(&spec, replica, &full, &[30000]).unwrap().gpu_ids[0])
+            .collect();
+
+        // ...then again after GPU 3 is removed. Only replicas that had
+        // hashed onto GPU 3 should move; the rest stay put.
+        let shrunk = candidates(&[(0, 16 * GB, 0), (1, 16 * GB, 0), (2, 16 * GB, 0)]);
+        let after: Vec<u32> = (0..8)
+            .map(|replica| RendezvousStrategy.place(&spec, replica, &shrunk, &[30000]).unwrap().gpu_ids[0])
+            .collect();
+
+        for (replica, (&b, &a)) in before.iter().zip(after.iter()).enumerate() {
+            if b != 3 {
+                assert_eq!(b, a, "replica {} moved despite its GPU surviving", replica);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rendezvous_insufficient_capacity() {
+        let mut spec = DeploymentSpec::new("test".to_string(), "ai/llama3:8b".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("32Gi".to_string());
+
+        let gpus = candidates(&[(0, 8 * GB, 0), (1, 16 * GB, 0)]);
+        assert!(RendezvousStrategy.place(&spec, 0, &gpus, &[30000]).is_none());
+    }
 }
@@ -1,21 +1,38 @@
 //! Main scheduler logic
 
 use dmrlet_core::{
-    detect_gpus, DeploymentSpec, DeploymentStatus, DmrletError, DmrletResult, Worker, WorkerStatus,
+    detect_gpus, DeploymentSpec, DeploymentStatus, DmrletError, DmrletResult, StorageConfig, Worker,
+    WorkerStatus,
 };
+use dmrlet_store::cache::{CacheStats, EvictionEvent, ExpiryPolicy, ScrubReport};
+use dmrlet_store::{CacheStore, MemoryCacheStore, ModelCache, SledCacheStore};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::gpu_allocator::GpuAllocator;
-use crate::placement::{DefaultPlacementStrategy, PlacementStrategy};
+use crate::layout::ClusterLayout;
+use crate::placement::{per_gpu_requirement, strategy_for, GpuCandidate, PlacementDecision};
+use crate::state::{MemoryStateStore, StateStore};
 
 type DeploymentsMap = HashMap<Uuid, DeploymentSpec>;
 type WorkersMap = HashMap<Uuid, Worker>;
 type PortsSet = std::collections::HashSet<u16>;
 
+/// Per-node resource pool: its own GPU allocator and port set, so allocation
+/// is scoped to a single physical host rather than shared globally.
+struct NodePool {
+    /// Reachable address advertised for workers placed here.
+    address: String,
+    /// GPU allocator over this node's devices.
+    gpu_allocator: GpuAllocator,
+    /// Ports currently in use on this node.
+    allocated_ports: PortsSet,
+}
+
 /// Scheduler manages deployments and worker placement
 pub struct Scheduler {
     /// Deployments indexed by ID
@@ -24,19 +41,51 @@ pub struct Scheduler {
     workers: RwLock<WorkersMap>,
     /// GPU allocator
     gpu_allocator: RwLock<GpuAllocator>,
-    /// Placement strategy
-    placement_strategy: Arc<dyn PlacementStrategy>,
     /// Base port for workers
     base_port: u16,
     /// Maximum port for workers
     max_port: u16,
     /// Allocated ports
     allocated_ports: RwLock<PortsSet>,
+    /// Cluster topology layout for multi-node placement
+    layout: RwLock<ClusterLayout>,
+    /// Per-node resource pools, populated as nodes register
+    node_pools: RwLock<HashMap<String, NodePool>>,
+    /// Local model cache, with LRU eviction bounded by `StorageConfig`
+    model_cache: Arc<ModelCache>,
+    /// Model reference each worker is pinning, so it can be released on stop
+    worker_models: RwLock<HashMap<Uuid, String>>,
+    /// Last heartbeat received from each worker, used by the reconciler
+    heartbeats: RwLock<HashMap<Uuid, Instant>>,
+    /// How stale a heartbeat may get before the worker is declared dead
+    heartbeat_timeout: Duration,
+    /// Deadline by which each draining worker's resources are released
+    /// unconditionally, keyed by worker id
+    drain_deadlines: RwLock<HashMap<Uuid, Instant>>,
+    /// Grace period given to a draining worker before it is torn down
+    /// regardless of reported in-flight requests
+    drain_grace_period: Duration,
+    /// Durable backing store for deployments and workers
+    store: Arc<dyn StateStore>,
 }
 
+/// Default interval between reconciliation passes.
+pub const DEFAULT_RECONCILE_INTERVAL: Duration = Duration::from_secs(10);
+/// Default heartbeat staleness threshold before a worker is marked failed.
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default grace period a draining worker is given to finish in-flight
+/// requests before its resources are forcibly released.
+pub const DEFAULT_DRAIN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+/// Default interval between model cache expiry sweeps.
+pub const DEFAULT_CACHE_EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(300);
+
 impl Scheduler {
-    /// Create a new scheduler
-    pub fn new(base_port: u16, max_workers: u32) -> Self {
+    /// Create a new scheduler backed by `store`, replaying any persisted state.
+    ///
+    /// Persisted deployments and workers are loaded into the in-memory maps and
+    /// their GPUs/ports are re-marked as allocated, so a restart adopts the
+    /// workers that are still running rather than orphaning them.
+    pub async fn new(base_port: u16, max_workers: u32, store: Arc<dyn StateStore>) -> Self {
         let gpu_info = detect_gpus();
         let max_port = base_port + max_workers as u16;
 
@@ -47,15 +96,198 @@ impl Scheduler {
             "Scheduler initialized"
         );
 
-        Self {
+        let scheduler = Self {
             deployments: RwLock::new(HashMap::new()),
             workers: RwLock::new(HashMap::new()),
             gpu_allocator: RwLock::new(GpuAllocator::new(gpu_info)),
-            placement_strategy: Arc::new(DefaultPlacementStrategy),
             base_port,
             max_port,
             allocated_ports: RwLock::new(std::collections::HashSet::new()),
+            layout: RwLock::new(ClusterLayout::new()),
+            node_pools: RwLock::new(HashMap::new()),
+            model_cache: Arc::new(Self::default_cache()),
+            worker_models: RwLock::new(HashMap::new()),
+            heartbeats: RwLock::new(HashMap::new()),
+            heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
+            drain_deadlines: RwLock::new(HashMap::new()),
+            drain_grace_period: DEFAULT_DRAIN_GRACE_PERIOD,
+            store,
+        };
+
+        if let Err(e) = scheduler.replay().await {
+            warn!(error = %e, "Failed to replay persisted scheduler state");
+        }
+
+        if let Err(e) = scheduler.model_cache.init().await {
+            warn!(error = %e, "Failed to initialize model cache from metadata store");
+        }
+
+        scheduler
+    }
+
+    /// Create a scheduler with an ephemeral in-memory store (no durability).
+    pub async fn ephemeral(base_port: u16, max_workers: u32) -> Self {
+        Self::new(base_port, max_workers, Arc::new(MemoryStateStore::new())).await
+    }
+
+    /// Override the heartbeat staleness threshold used by the reconciler.
+    pub fn with_heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// Override the grace period a draining worker is given before forced
+    /// teardown.
+    pub fn with_drain_grace_period(mut self, grace_period: Duration) -> Self {
+        self.drain_grace_period = grace_period;
+        self
+    }
+
+    /// Rebuild in-memory maps from the durable store.
+    async fn replay(&self) -> DmrletResult<()> {
+        let deployments = self.store.load_deployments().await?;
+        let workers = self.store.load_workers().await?;
+
+        let deployment_count = deployments.len();
+        let worker_count = workers.len();
+
+        {
+            let mut map = self.deployments.write().await;
+            for spec in deployments {
+                map.insert(spec.id, spec);
+            }
+        }
+
+        {
+            let mut map = self.workers.write().await;
+            let mut ports = self.allocated_ports.write().await;
+            let mut allocator = self.gpu_allocator.write().await;
+            let mut pins = self.worker_models.write().await;
+            let specs: HashMap<Uuid, DeploymentSpec> = {
+                let deployments = self.deployments.read().await;
+                deployments.iter().map(|(id, spec)| (*id, spec.clone())).collect()
+            };
+            for mut worker in workers {
+                ports.insert(worker.endpoint.port);
+                if !worker.gpu_ids.is_empty() {
+                    // Reserve the worker's actual GPUs (not just a count) so the
+                    // allocator's bookkeeping agrees with reality; see
+                    // `reserve_decision` for why this must mirror the original
+                    // placement rather than re-derive a set by index.
+                    let _ = allocator.allocate_specific(&worker.gpu_ids);
+                    if let Some(spec) = specs.get(&worker.deployment_id) {
+                        let per_gpu = per_gpu_requirement(spec, worker.gpu_ids.len());
+                        if per_gpu > 0 {
+                            worker.gpu_reservation_ids = worker
+                                .gpu_ids
+                                .iter()
+                                .filter_map(|&gpu| {
+                                    allocator.reserve_memory_on(gpu, per_gpu).ok().map(|r| r.id)
+                                })
+                                .collect();
+                        }
+                    }
+                }
+                if let Some(spec) = specs.get(&worker.deployment_id) {
+                    *pins.entry(worker.id).or_default() = spec.model.clone();
+                }
+                map.insert(worker.id, worker);
+            }
+        }
+
+        // Re-pin each replayed worker's model in the cache.
+        let pins = self.worker_models.read().await.clone();
+        for model in pins.values() {
+            self.model_cache.acquire(model).await;
         }
+
+        // Give replayed workers a fresh heartbeat grace period so the
+        // reconciler doesn't immediately reap them before they re-register.
+        {
+            let now = Instant::now();
+            let ids: Vec<Uuid> = self.workers.read().await.keys().copied().collect();
+            let mut beats = self.heartbeats.write().await;
+            for id in ids {
+                beats.insert(id, now);
+            }
+        }
+
+        if deployment_count > 0 || worker_count > 0 {
+            info!(
+                deployments = deployment_count,
+                workers = worker_count,
+                "Replayed persisted scheduler state"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Build the default model cache from [`StorageConfig`].
+    ///
+    /// Metadata is kept in an embedded sled database under the cache
+    /// directory so `init` can reconcile it against what's actually on disk
+    /// after a restart; if it fails to open, the cache still works for the
+    /// running process but loses durability across restarts.
+    fn default_cache() -> ModelCache {
+        let storage = StorageConfig::default();
+        let metadata_path = storage.models_path.join(".metadata");
+        let store: Arc<dyn CacheStore> = match SledCacheStore::open(&metadata_path) {
+            Ok(store) => Arc::new(store),
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    path = %metadata_path.display(),
+                    "Failed to open cache metadata store; falling back to in-memory (no persistence across restarts)"
+                );
+                Arc::new(MemoryCacheStore::new())
+            }
+        };
+        let policy = ExpiryPolicy {
+            max_idle: storage.max_idle_secs.map(Duration::from_secs),
+            max_ttl: storage.max_ttl_secs.map(Duration::from_secs),
+            max_idle_overrides: HashMap::new(),
+        };
+
+        ModelCache::new(storage.models_path, storage.max_cache_size, storage.lru_eviction, store)
+            .with_expiry_policy(policy)
+    }
+
+    /// Shared handle to the local model cache.
+    pub fn model_cache(&self) -> Arc<ModelCache> {
+        Arc::clone(&self.model_cache)
+    }
+
+    /// Spawn the background cache expiry sweep loop.
+    ///
+    /// Returns the task handle so the daemon can hold (or abort) it, mirroring
+    /// [`spawn_reconciler`](Self::spawn_reconciler).
+    pub fn spawn_cache_expiry_sweeper(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        Arc::clone(&self.model_cache).spawn_expiry_sweeper(interval)
+    }
+
+    /// Current cache usage statistics.
+    pub async fn cache_stats(&self) -> CacheStats {
+        self.model_cache.stats().await
+    }
+
+    /// Recent model eviction events.
+    pub async fn cache_evictions(&self) -> Vec<EvictionEvent> {
+        self.model_cache.evictions().await
+    }
+
+    /// Scrub the model cache for missing, corrupt, or orphaned blobs and
+    /// repair what it finds. See [`ModelCache::scrub`].
+    pub async fn cache_scrub(&self) -> DmrletResult<ScrubReport> {
+        self.model_cache.scrub().await
+    }
+
+    /// Mutable access to the cluster topology layout.
+    ///
+    /// Operators register nodes and toggle zone/capacity/tag metadata through
+    /// this handle; the returned guard bumps the layout version on change.
+    pub fn layout(&self) -> &RwLock<ClusterLayout> {
+        &self.layout
     }
 
     /// Create a new deployment
@@ -72,6 +304,9 @@ impl Scheduler {
 
         // Store the deployment
         self.deployments.write().await.insert(id, spec.clone());
+        if let Err(e) = self.store.save_deployment(&spec).await {
+            warn!(deployment_id = %id, error = %e, "Failed to persist deployment");
+        }
 
         // Create workers for the deployment
         for i in 0..spec.replicas {
@@ -95,29 +330,32 @@ impl Scheduler {
             }
         }
 
+        #[cfg(feature = "metrics")]
+        self.record_deployment_workers(id).await;
+
         Ok(id)
     }
 
+    /// Publish the number of active workers for a deployment as a gauge.
+    #[cfg(feature = "metrics")]
+    async fn record_deployment_workers(&self, id: Uuid) {
+        let count = {
+            let workers = self.workers.read().await;
+            workers.values().filter(|w| w.deployment_id == id).count()
+        };
+        dmrlet_core::metrics::gauge_set(
+            "dmrlet_deployment_workers",
+            &[("deployment", id.to_string().as_str())],
+            count as f64,
+        );
+    }
+
     /// Schedule a worker for a deployment
     async fn schedule_worker(
         &self,
         spec: &DeploymentSpec,
         worker_index: u32,
     ) -> DmrletResult<Worker> {
-        // Get available resources
-        let available_gpus: Vec<u32> = {
-            let allocator = self.gpu_allocator.read().await;
-            allocator
-                .get_gpu_info()
-                .iter()
-                .filter(|s| !s.allocated)
-                .map(|s| s.device.index)
-                .collect()
-        };
-
-        let available_ports = self.get_available_ports().await;
-
-        // Make placement decision
         let existing_workers = {
             let workers = self.workers.read().await;
             workers
@@ -126,29 +364,246 @@ impl Scheduler {
                 .count() as u32
         };
 
-        let decision = self
-            .placement_strategy
-            .place(spec, existing_workers, &available_gpus, &available_ports)
-            .ok_or_else(|| {
-                DmrletError::ResourceExhausted("No resources available for worker".to_string())
-            })?;
+        let strategy = strategy_for(spec.placement_strategy);
 
-        // Allocate resources
-        if spec.resources.gpu_count > 0 {
-            let mut allocator = self.gpu_allocator.write().await;
-            allocator.allocate(spec.resources.gpu_count)?;
+        // Multi-node placement when nodes have registered; otherwise fall back
+        // to the local single-host allocator.
+        let worker = if self.node_pools.read().await.is_empty() {
+            self.place_local(spec, worker_index, existing_workers, strategy.as_ref())
+                .await?
+        } else {
+            self.place_on_node(spec, worker_index, existing_workers, strategy.as_ref())
+                .await?
+        };
+
+        // Pin the worker's model in the cache so it survives LRU eviction for
+        // as long as this worker is running.
+        self.model_cache.acquire(&spec.model).await;
+        self.worker_models
+            .write()
+            .await
+            .insert(worker.id, spec.model.clone());
+
+        // Seed the heartbeat so a freshly placed worker has a grace period to
+        // come up before the reconciler considers it stale.
+        self.heartbeats.write().await.insert(worker.id, Instant::now());
+
+        if let Err(e) = self.store.save_worker(&worker).await {
+            warn!(worker_id = %worker.id, error = %e, "Failed to persist worker");
         }
 
-        // Allocate port
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::counter_inc("dmrlet_scheduler_placements_total", &[]);
+
+        Ok(worker)
+    }
+
+    /// Register (or refresh) a cluster node and its GPU inventory.
+    ///
+    /// The node joins the placement layout and gets a dedicated [`NodePool`]
+    /// with its own GPU allocator and port set, so subsequent placements are
+    /// scoped to individual hosts rather than the local machine.
+    pub async fn register_node(
+        &self,
+        mut descriptor: crate::layout::NodeDescriptor,
+        gpu_info: dmrlet_core::GpuInfo,
+    ) {
+        descriptor.last_seen = Some(chrono::Utc::now());
+        let id = descriptor.id.clone();
+        let address = descriptor.address.clone();
+
+        info!(node = %id, address = %address, gpus = gpu_info.total_count, "Registering node");
+
+        self.layout.write().await.upsert_node(descriptor);
+
+        let mut pools = self.node_pools.write().await;
+        pools
+            .entry(id)
+            .and_modify(|pool| pool.address = address.clone())
+            .or_insert_with(|| NodePool {
+                address,
+                gpu_allocator: GpuAllocator::new(gpu_info),
+                allocated_ports: PortsSet::new(),
+            });
+    }
+
+    /// Place a worker on the local host using the global allocator.
+    async fn place_local(
+        &self,
+        spec: &DeploymentSpec,
+        worker_index: u32,
+        existing_workers: u32,
+        strategy: &dyn crate::placement::PlacementStrategy,
+    ) -> DmrletResult<Worker> {
+        let gpu_load = self.gpu_load(|_| true).await;
+
+        let candidates: Vec<GpuCandidate> = {
+            let allocator = self.gpu_allocator.read().await;
+            free_candidates(&allocator, &gpu_load)
+        };
+
+        let available_ports = self.get_available_ports().await;
+
+        let decision = match strategy.place(spec, existing_workers, &candidates, &available_ports) {
+            Some(decision) => decision,
+            None => return self.placement_exhausted(),
+        };
+
+        let reservation_ids =
+            reserve_decision(&mut self.gpu_allocator.write().await, spec, &decision)?;
         self.allocated_ports.write().await.insert(decision.port);
 
-        // Create worker
         let mut worker = Worker::new(spec.id, worker_index, decision.port);
         worker.gpu_ids = decision.gpu_ids;
+        worker.gpu_reservation_ids = reservation_ids;
+        Ok(worker)
+    }
+
+    /// Place a worker on a registered cluster node, allocating from that node's
+    /// own GPU and port pools.
+    async fn place_on_node(
+        &self,
+        spec: &DeploymentSpec,
+        worker_index: u32,
+        existing_workers: u32,
+        strategy: &dyn crate::placement::PlacementStrategy,
+    ) -> DmrletResult<Worker> {
+        // Honor zone/capacity/tag constraints to pick the target node.
+        let node_id = self
+            .layout
+            .read()
+            .await
+            .place_replicas(1, &spec.resources.placement_tags)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                DmrletError::ResourceExhausted(
+                    "No cluster node satisfies placement constraints".to_string(),
+                )
+            })?;
+
+        let gpu_load = self
+            .gpu_load(|w| w.node_id.as_deref() == Some(node_id.as_str()))
+            .await;
 
+        let mut pools = self.node_pools.write().await;
+        let pool = pools
+            .get_mut(&node_id)
+            .ok_or_else(|| DmrletError::Config(format!("Unknown node: {}", node_id)))?;
+
+        let candidates = free_candidates(&pool.gpu_allocator, &gpu_load);
+        let available_ports: Vec<u16> = (self.base_port..self.max_port)
+            .filter(|p| !pool.allocated_ports.contains(p))
+            .collect();
+
+        let mut decision = match strategy.place(spec, existing_workers, &candidates, &available_ports) {
+            Some(decision) => decision,
+            None => return self.placement_exhausted(),
+        };
+        decision.node_id = Some(node_id);
+
+        let reservation_ids = reserve_decision(&mut pool.gpu_allocator, spec, &decision)?;
+        pool.allocated_ports.insert(decision.port);
+
+        let mut worker = Worker::new(spec.id, worker_index, decision.port);
+        worker.gpu_ids = decision.gpu_ids;
+        worker.gpu_reservation_ids = reservation_ids;
+        worker.node_id = decision.node_id;
+        if !pool.address.is_empty() {
+            worker.endpoint.host = pool.address.clone();
+        }
         Ok(worker)
     }
 
+    /// Count co-located workers per GPU index among workers matching `filter`.
+    async fn gpu_load(&self, filter: impl Fn(&Worker) -> bool) -> HashMap<u32, u32> {
+        let workers = self.workers.read().await;
+        let mut load: HashMap<u32, u32> = HashMap::new();
+        for w in workers.values().filter(|w| filter(w)) {
+            for gpu in &w.gpu_ids {
+                *load.entry(*gpu).or_default() += 1;
+            }
+        }
+        load
+    }
+
+    /// Record a placement failure and return the typed error.
+    fn placement_exhausted(&self) -> DmrletResult<Worker> {
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::counter_inc("dmrlet_scheduler_placement_failures_total", &[]);
+        Err(DmrletError::ResourceExhausted(
+            "No resources available for worker".to_string(),
+        ))
+    }
+
+    /// Refresh the process-global registry with the scheduler's current state.
+    ///
+    /// Called on each `/metrics` scrape. Counters (placement successes and
+    /// failures) are incremented at their event sites; the gauges below reflect
+    /// live state and are therefore recomputed here rather than on every change.
+    #[cfg(feature = "metrics")]
+    pub async fn export_metrics(&self) {
+        use dmrlet_core::metrics::gauge_set;
+
+        let deployments = self.deployments.read().await;
+        let workers = self.workers.read().await;
+
+        gauge_set("dmrlet_deployments", &[], deployments.len() as f64);
+
+        for spec in deployments.values() {
+            let id = spec.id.to_string();
+            let labels = [("deployment", id.as_str()), ("model", spec.model.as_str())];
+            let mut total = 0u64;
+            let mut ready = 0u64;
+            let mut failed = 0u64;
+            for w in workers.values().filter(|w| w.deployment_id == spec.id) {
+                total += 1;
+                match w.status {
+                    WorkerStatus::Running => ready += 1,
+                    WorkerStatus::Failed => failed += 1,
+                    _ => {}
+                }
+            }
+            gauge_set("dmrlet_deployment_workers_total", &labels, total as f64);
+            gauge_set("dmrlet_deployment_workers_ready", &labels, ready as f64);
+            gauge_set("dmrlet_deployment_workers_failed", &labels, failed as f64);
+        }
+
+        let gpus = self.gpu_allocator.read().await;
+        let slots = gpus.get_gpu_info();
+        let total_gpus = slots.len();
+        let free_gpus = slots.iter().filter(|s| !s.allocated).count();
+        gauge_set("dmrlet_gpus_total", &[], total_gpus as f64);
+        gauge_set("dmrlet_gpus_free", &[], free_gpus as f64);
+
+        for slot in &slots {
+            let index = slot.device.index.to_string();
+            let labels = [("gpu", index.as_str())];
+            gauge_set("dmrlet_gpu_memory_total_bytes", &labels, slot.device.memory_total as f64);
+            gauge_set("dmrlet_gpu_memory_free_bytes", &labels, slot.free_bytes as f64);
+            gauge_set(
+                "dmrlet_gpu_available",
+                &labels,
+                if slot.allocated { 0.0 } else { 1.0 },
+            );
+        }
+
+        let allocated_ports = self.allocated_ports.read().await.len();
+        let total_ports = (self.max_port - self.base_port) as usize;
+        gauge_set("dmrlet_ports_allocated", &[], allocated_ports as f64);
+        gauge_set(
+            "dmrlet_ports_available",
+            &[],
+            total_ports.saturating_sub(allocated_ports) as f64,
+        );
+
+        let cache = self.model_cache.stats().await;
+        gauge_set("dmrlet_cache_size_bytes", &[], cache.total_size as f64);
+        gauge_set("dmrlet_cache_max_size_bytes", &[], cache.max_size as f64);
+        gauge_set("dmrlet_cache_models", &[], cache.model_count as f64);
+        gauge_set("dmrlet_cache_utilization_percent", &[], cache.utilization);
+    }
+
     /// Get available ports
     async fn get_available_ports(&self) -> Vec<u16> {
         let allocated = self.allocated_ports.read().await;
@@ -157,9 +612,13 @@ impl Scheduler {
             .collect()
     }
 
-    /// Delete a deployment
-    pub async fn delete_deployment(&self, id: Uuid) -> DmrletResult<()> {
-        info!(deployment_id = %id, "Deleting deployment");
+    /// Delete a deployment.
+    ///
+    /// With `force` set, every worker is torn down and its resources released
+    /// immediately. Otherwise each worker is handed to [`Self::begin_drain`]
+    /// so in-flight requests finish before it disappears.
+    pub async fn delete_deployment(&self, id: Uuid, force: bool) -> DmrletResult<()> {
+        info!(deployment_id = %id, force, "Deleting deployment");
 
         // Remove deployment
         let spec = self.deployments.write().await.remove(&id);
@@ -179,9 +638,24 @@ impl Scheduler {
         };
 
         for worker_id in worker_ids {
-            self.remove_worker(worker_id).await?;
+            if force {
+                self.remove_worker(worker_id).await?;
+            } else {
+                self.begin_drain(worker_id).await?;
+            }
         }
 
+        if let Err(e) = self.store.delete_deployment(id).await {
+            warn!(deployment_id = %id, error = %e, "Failed to delete persisted deployment");
+        }
+
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::gauge_set(
+            "dmrlet_deployment_workers",
+            &[("deployment", id.to_string().as_str())],
+            0.0,
+        );
+
         Ok(())
     }
 
@@ -190,14 +664,47 @@ impl Scheduler {
         let worker = self.workers.write().await.remove(&worker_id);
 
         if let Some(w) = worker {
-            // Release GPUs
-            if !w.gpu_ids.is_empty() {
-                let mut allocator = self.gpu_allocator.write().await;
-                allocator.release(&w.gpu_ids);
+            // Release GPUs and port from the pool the worker was placed from:
+            // its node's pool when topology is configured, the global pool
+            // otherwise.
+            match &w.node_id {
+                Some(node_id) => {
+                    let mut pools = self.node_pools.write().await;
+                    if let Some(pool) = pools.get_mut(node_id) {
+                        if !w.gpu_ids.is_empty() {
+                            pool.gpu_allocator.release(&w.gpu_ids);
+                        }
+                        for reservation_id in &w.gpu_reservation_ids {
+                            pool.gpu_allocator.release_memory_by_id(*reservation_id);
+                        }
+                        pool.allocated_ports.remove(&w.endpoint.port);
+                    }
+                }
+                None => {
+                    if !w.gpu_ids.is_empty() {
+                        self.gpu_allocator.write().await.release(&w.gpu_ids);
+                    }
+                    for reservation_id in &w.gpu_reservation_ids {
+                        self.gpu_allocator
+                            .write()
+                            .await
+                            .release_memory_by_id(*reservation_id);
+                    }
+                    self.allocated_ports.write().await.remove(&w.endpoint.port);
+                }
             }
 
-            // Release port
-            self.allocated_ports.write().await.remove(&w.endpoint.port);
+            // Release the worker's model pin so it becomes eligible for LRU
+            // eviction once no other worker references it.
+            if let Some(model) = self.worker_models.write().await.remove(&worker_id) {
+                self.model_cache.release(&model).await;
+            }
+
+            self.heartbeats.write().await.remove(&worker_id);
+
+            if let Err(e) = self.store.remove_worker(worker_id).await {
+                warn!(worker_id = %worker_id, error = %e, "Failed to delete persisted worker");
+            }
 
             debug!(worker_id = %worker_id, "Worker removed");
         }
@@ -205,8 +712,34 @@ impl Scheduler {
         Ok(())
     }
 
+    /// Transition a worker into `Draining` instead of tearing it down.
+    ///
+    /// A draining worker keeps its GPUs and port reserved and stays out of
+    /// [`Self::get_all_endpoints`] so it receives no new traffic, but its
+    /// in-flight requests can finish. [`Self::reconcile`] finalizes the drain
+    /// — releasing the worker's resources via [`Self::remove_worker`] — once
+    /// its grace period elapses or it reports zero active requests.
+    async fn begin_drain(&self, worker_id: Uuid) -> DmrletResult<()> {
+        {
+            let mut workers = self.workers.write().await;
+            let worker = workers
+                .get_mut(&worker_id)
+                .ok_or_else(|| DmrletError::WorkerNotFound(worker_id.to_string()))?;
+            worker.status = WorkerStatus::Draining;
+        }
+        self.drain_deadlines
+            .write()
+            .await
+            .insert(worker_id, Instant::now() + self.drain_grace_period);
+        debug!(worker_id = %worker_id, "Worker draining");
+        Ok(())
+    }
+
     /// Scale a deployment
-    pub async fn scale_deployment(&self, id: Uuid, replicas: u32) -> DmrletResult<()> {
+    ///
+    /// With `force` set, workers removed by a scale-down are torn down
+    /// immediately; otherwise they are handed to [`Self::begin_drain`].
+    pub async fn scale_deployment(&self, id: Uuid, replicas: u32, force: bool) -> DmrletResult<()> {
         let spec = {
             let mut deployments = self.deployments.write().await;
             let spec = deployments
@@ -216,11 +749,27 @@ impl Scheduler {
             spec.clone()
         };
 
+        if let Err(e) = self.store.save_deployment(&spec).await {
+            warn!(deployment_id = %id, error = %e, "Failed to persist scaled deployment");
+        }
+
+        // Only live (non-draining, non-terminal) workers count toward the
+        // replica total, so a worker already draining from a previous
+        // scale-down isn't double-counted or redrained.
         let current_workers: Vec<Worker> = {
             let workers = self.workers.read().await;
             workers
                 .values()
                 .filter(|w| w.deployment_id == id)
+                .filter(|w| {
+                    !matches!(
+                        w.status,
+                        WorkerStatus::Draining
+                            | WorkerStatus::Terminating
+                            | WorkerStatus::Terminated
+                            | WorkerStatus::Failed
+                    )
+                })
                 .cloned()
                 .collect()
         };
@@ -249,7 +798,11 @@ impl Scheduler {
                 .collect();
 
             for worker_id in workers_to_remove {
-                self.remove_worker(worker_id).await?;
+                if force {
+                    self.remove_worker(worker_id).await?;
+                } else {
+                    self.begin_drain(worker_id).await?;
+                }
             }
         }
 
@@ -259,6 +812,9 @@ impl Scheduler {
             "Deployment scaled"
         );
 
+        #[cfg(feature = "metrics")]
+        self.record_deployment_workers(id).await;
+
         Ok(())
     }
 
@@ -329,14 +885,273 @@ impl Scheduler {
         worker_id: Uuid,
         status: WorkerStatus,
     ) -> DmrletResult<()> {
-        let mut workers = self.workers.write().await;
-        if let Some(worker) = workers.get_mut(&worker_id) {
-            worker.status = status;
-            Ok(())
-        } else {
-            Err(DmrletError::WorkerNotFound(worker_id.to_string()))
+        let worker = {
+            let mut workers = self.workers.write().await;
+            match workers.get_mut(&worker_id) {
+                Some(worker) => {
+                    worker.status = status;
+                    worker.clone()
+                }
+                None => return Err(DmrletError::WorkerNotFound(worker_id.to_string())),
+            }
+        };
+
+        if let Err(e) = self.store.save_worker(&worker).await {
+            warn!(worker_id = %worker_id, error = %e, "Failed to persist worker status");
+        }
+        Ok(())
+    }
+
+    /// Record a heartbeat from a worker.
+    ///
+    /// Refreshes the worker's liveness timestamp and advances it out of the
+    /// startup states (`Pending`/`Starting`) into `Running`, which is how a
+    /// worker signals it is serving traffic.
+    ///
+    /// `active_requests`, when reported, updates the worker's in-flight count
+    /// so a draining worker can be torn down as soon as it reaches zero
+    /// rather than waiting out the full grace period.
+    pub async fn heartbeat(&self, worker_id: Uuid, active_requests: Option<u32>) -> DmrletResult<()> {
+        {
+            let mut workers = self.workers.write().await;
+            let worker = workers
+                .get_mut(&worker_id)
+                .ok_or_else(|| DmrletError::WorkerNotFound(worker_id.to_string()))?;
+            if matches!(
+                worker.status,
+                WorkerStatus::Pending | WorkerStatus::Starting
+            ) {
+                worker.status = WorkerStatus::Running;
+            }
+            if let Some(count) = active_requests {
+                worker.active_requests = count;
+            }
+        }
+        self.heartbeats.write().await.insert(worker_id, Instant::now());
+        Ok(())
+    }
+
+    /// Spawn the background reconciliation loop.
+    ///
+    /// Returns the task handle so the daemon can hold (or abort) it. Each tick
+    /// runs one [`reconcile`](Self::reconcile) pass.
+    pub fn spawn_reconciler(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.reconcile().await;
+            }
+        })
+    }
+
+    /// Run a single reconciliation pass.
+    ///
+    /// First finalizes workers that finished draining, then reaps workers
+    /// whose heartbeat has gone stale (marking them [`WorkerStatus::Failed`]
+    /// and releasing their resources), then tops each deployment back up to
+    /// its desired replica count. The pass is idempotent: replica accounting
+    /// counts every non-terminal worker, so a slot that was just refilled is
+    /// not refilled again on the next tick.
+    pub async fn reconcile(&self) {
+        let now = Instant::now();
+
+        // 0. Finalize workers that have drained: either the grace period
+        // elapsed or the worker reported it has no in-flight requests left.
+        let finished_draining: Vec<Uuid> = {
+            let workers = self.workers.read().await;
+            let deadlines = self.drain_deadlines.read().await;
+            workers
+                .values()
+                .filter(|w| w.status == WorkerStatus::Draining)
+                .filter(|w| {
+                    w.active_requests == 0
+                        || !matches!(deadlines.get(&w.id), Some(deadline) if now < *deadline)
+                })
+                .map(|w| w.id)
+                .collect()
+        };
+
+        for worker_id in finished_draining {
+            debug!(worker_id = %worker_id, "Drain finished; releasing worker resources");
+            self.drain_deadlines.write().await.remove(&worker_id);
+            if let Err(e) = self.remove_worker(worker_id).await {
+                warn!(worker_id = %worker_id, error = %e, "Failed to remove drained worker");
+            }
+        }
+
+        // 1. Reap workers that missed their heartbeat deadline.
+        let stale: Vec<Uuid> = {
+            let workers = self.workers.read().await;
+            let beats = self.heartbeats.read().await;
+            workers
+                .values()
+                .filter(|w| {
+                    !matches!(
+                        w.status,
+                        WorkerStatus::Terminating
+                            | WorkerStatus::Terminated
+                            | WorkerStatus::Failed
+                    )
+                })
+                .filter(|w| match beats.get(&w.id) {
+                    Some(last) => now.duration_since(*last) > self.heartbeat_timeout,
+                    None => true,
+                })
+                .map(|w| w.id)
+                .collect()
+        };
+
+        for worker_id in stale {
+            warn!(worker_id = %worker_id, "Worker heartbeat timed out; marking failed");
+            let _ = self.update_worker_status(worker_id, WorkerStatus::Failed).await;
+            if let Err(e) = self.remove_worker(worker_id).await {
+                warn!(worker_id = %worker_id, error = %e, "Failed to reap dead worker");
+            }
+        }
+
+        // 2. Drive each deployment back toward its desired replica count.
+        let specs: Vec<DeploymentSpec> = self.deployments.read().await.values().cloned().collect();
+        for spec in specs {
+            let live: Vec<u32> = {
+                let workers = self.workers.read().await;
+                workers
+                    .values()
+                    .filter(|w| w.deployment_id == spec.id)
+                    .filter(|w| {
+                        !matches!(
+                            w.status,
+                            WorkerStatus::Terminating
+                                | WorkerStatus::Terminated
+                                | WorkerStatus::Failed
+                        )
+                    })
+                    .map(|w| w.index)
+                    .collect()
+            };
+
+            if (live.len() as u32) >= spec.replicas {
+                continue;
+            }
+
+            // Fill the lowest-numbered replica slots that are not currently live.
+            let missing: Vec<u32> = (0..spec.replicas)
+                .filter(|i| !live.contains(i))
+                .take((spec.replicas - live.len() as u32) as usize)
+                .collect();
+
+            for index in missing {
+                match self.schedule_worker(&spec, index).await {
+                    Ok(worker) => {
+                        info!(
+                            deployment_id = %spec.id,
+                            worker_id = %worker.id,
+                            worker_index = index,
+                            "Reconciler replaced missing worker"
+                        );
+                        self.workers.write().await.insert(worker.id, worker);
+                    }
+                    Err(e) => warn!(
+                        deployment_id = %spec.id,
+                        worker_index = index,
+                        error = %e,
+                        "Reconciler failed to replace worker"
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Mark a node draining and migrate its workers elsewhere.
+    ///
+    /// The node stops receiving new placements, and every worker currently on
+    /// it is torn down and rescheduled so each deployment keeps its requested
+    /// replica count. Workers that cannot be re-placed (no remaining capacity)
+    /// are left removed and surface as a degraded deployment.
+    pub async fn drain_node(&self, node_id: &str) -> DmrletResult<()> {
+        if !self.layout.write().await.set_draining(node_id, true) {
+            return Err(DmrletError::Config(format!("Unknown node: {}", node_id)));
         }
+
+        let affected: Vec<Worker> = {
+            let workers = self.workers.read().await;
+            workers
+                .values()
+                .filter(|w| w.node_id.as_deref() == Some(node_id))
+                .cloned()
+                .collect()
+        };
+
+        info!(node = %node_id, workers = affected.len(), "Draining node");
+
+        for worker in affected {
+            let spec = {
+                let deployments = self.deployments.read().await;
+                deployments.get(&worker.deployment_id).cloned()
+            };
+
+            self.remove_worker(worker.id).await?;
+
+            if let Some(spec) = spec {
+                match self.schedule_worker(&spec, worker.index).await {
+                    Ok(replacement) => {
+                        self.workers.write().await.insert(replacement.id, replacement);
+                    }
+                    Err(e) => warn!(
+                        deployment_id = %worker.deployment_id,
+                        error = %e,
+                        "Failed to migrate worker off draining node"
+                    ),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reserve exactly the GPUs a placement decision chose: the whole devices in
+/// `decision.gpu_ids`, plus an even share of any requested memory on each.
+/// Returns the memory reservation ids so the worker can release them later.
+///
+/// Reserving the decision's own GPUs (rather than re-deriving a set from
+/// `spec.resources.gpu_count`) keeps the allocator's bookkeeping in agreement
+/// with whatever the placement strategy actually picked.
+fn reserve_decision(
+    allocator: &mut GpuAllocator,
+    spec: &DeploymentSpec,
+    decision: &PlacementDecision,
+) -> DmrletResult<Vec<Uuid>> {
+    allocator.allocate_specific(&decision.gpu_ids)?;
+
+    if decision.gpu_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let per_gpu = per_gpu_requirement(spec, decision.gpu_ids.len());
+    if per_gpu == 0 {
+        return Ok(Vec::new());
     }
+
+    decision
+        .gpu_ids
+        .iter()
+        .map(|&gpu| allocator.reserve_memory_on(gpu, per_gpu).map(|r| r.id))
+        .collect()
+}
+
+/// Build placement candidates from an allocator's free GPUs, carrying each
+/// device's free memory and current co-located worker load.
+fn free_candidates(allocator: &GpuAllocator, gpu_load: &HashMap<u32, u32>) -> Vec<GpuCandidate> {
+    allocator
+        .get_gpu_info()
+        .iter()
+        .filter(|s| !s.allocated)
+        .map(|s| GpuCandidate {
+            index: s.device.index,
+            free_memory: s.free_bytes,
+            worker_load: gpu_load.get(&s.device.index).copied().unwrap_or(0),
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -345,7 +1160,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_deployment() {
-        let scheduler = Scheduler::new(30000, 100);
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
         let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
 
         let id = scheduler.create_deployment(spec).await.unwrap();
@@ -356,11 +1171,11 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_deployment() {
-        let scheduler = Scheduler::new(30000, 100);
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
         let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
 
         let id = scheduler.create_deployment(spec).await.unwrap();
-        scheduler.delete_deployment(id).await.unwrap();
+        scheduler.delete_deployment(id, true).await.unwrap();
 
         let result = scheduler.get_deployment_status(id).await;
         assert!(result.is_err());
@@ -368,26 +1183,77 @@ mod tests {
 
     #[tokio::test]
     async fn test_scale_deployment() {
-        let scheduler = Scheduler::new(30000, 100);
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
         let mut spec = DeploymentSpec::new("test".to_string(), "model".to_string());
         spec.replicas = 2;
 
         let id = scheduler.create_deployment(spec).await.unwrap();
 
         // Scale up
-        scheduler.scale_deployment(id, 4).await.unwrap();
+        scheduler.scale_deployment(id, 4, true).await.unwrap();
         let status = scheduler.get_deployment_status(id).await.unwrap();
         assert_eq!(status.spec.replicas, 4);
 
         // Scale down
-        scheduler.scale_deployment(id, 1).await.unwrap();
+        scheduler.scale_deployment(id, 1, true).await.unwrap();
         let status = scheduler.get_deployment_status(id).await.unwrap();
         assert_eq!(status.spec.replicas, 1);
+        assert_eq!(scheduler.get_workers(id).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scale_down_drains_instead_of_removing() {
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
+        let mut spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        spec.replicas = 2;
+
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        scheduler.scale_deployment(id, 1, false).await.unwrap();
+
+        // The removed replica lingers as Draining rather than disappearing...
+        let workers = scheduler.get_workers(id).await;
+        assert_eq!(workers.len(), 2);
+        assert_eq!(
+            workers
+                .iter()
+                .filter(|w| w.status == WorkerStatus::Draining)
+                .count(),
+            1
+        );
+
+        // ...until reconcile finalizes the drain once its grace period elapses.
+        scheduler
+            .drain_deadlines
+            .write()
+            .await
+            .values_mut()
+            .for_each(|deadline| *deadline = Instant::now());
+        scheduler.reconcile().await;
+        assert_eq!(scheduler.get_workers(id).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_draining_worker_released_on_zero_active_requests() {
+        let scheduler = Scheduler::ephemeral(30000, 100)
+            .await
+            .with_drain_grace_period(Duration::from_secs(3600));
+        let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        let worker = scheduler.get_workers(id).await.into_iter().next().unwrap();
+
+        scheduler.delete_deployment(id, false).await.unwrap();
+        scheduler.heartbeat(worker.id, Some(0)).await.unwrap();
+
+        // Zero in-flight requests finalizes the drain well before the grace
+        // period (an hour away) would have.
+        scheduler.reconcile().await;
+        assert!(scheduler.get_workers(id).await.is_empty());
     }
 
     #[tokio::test]
     async fn test_list_deployments() {
-        let scheduler = Scheduler::new(30000, 100);
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
 
         let spec1 = DeploymentSpec::new("test1".to_string(), "model1".to_string());
         let spec2 = DeploymentSpec::new("test2".to_string(), "model2".to_string());
@@ -398,4 +1264,194 @@ mod tests {
         let list = scheduler.list_deployments().await;
         assert_eq!(list.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_heartbeat_promotes_to_running() {
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
+        let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        let worker = scheduler.get_workers(id).await.into_iter().next().unwrap();
+        assert_eq!(worker.status, WorkerStatus::Pending);
+
+        scheduler.heartbeat(worker.id, None).await.unwrap();
+        let worker = scheduler.get_workers(id).await.into_iter().next().unwrap();
+        assert_eq!(worker.status, WorkerStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_replaces_stale_worker() {
+        // Zero timeout makes every worker immediately stale on the next pass.
+        let scheduler = Scheduler::ephemeral(30000, 100)
+            .await
+            .with_heartbeat_timeout(Duration::ZERO);
+        let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        let original = scheduler.get_workers(id).await.into_iter().next().unwrap();
+
+        scheduler.reconcile().await;
+
+        let workers = scheduler.get_workers(id).await;
+        assert_eq!(workers.len(), 1);
+        assert_ne!(workers[0].id, original.id);
+    }
+
+    fn test_gpu_info(count: u32) -> dmrlet_core::GpuInfo {
+        let devices = (0..count)
+            .map(|i| dmrlet_core::GpuDevice {
+                index: i,
+                name: format!("Test GPU {}", i),
+                memory_total: 16 * 1024 * 1024 * 1024,
+                memory_free: 16 * 1024 * 1024 * 1024,
+                vendor: dmrlet_core::GpuVendor::Nvidia,
+                available: true,
+                utilization: Some(0),
+                pci: None,
+                temperature_c: None,
+                power_usage_mw: None,
+                power_limit_mw: None,
+                processes: Vec::new(),
+            })
+            .collect();
+
+        dmrlet_core::GpuInfo {
+            total_count: count,
+            available_count: count,
+            devices,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_node_places_worker_on_node() {
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
+        let node =
+            crate::layout::NodeDescriptor::new("node-a", "zone-1", 1).with_address("10.0.0.5");
+        scheduler.register_node(node, test_gpu_info(2)).await;
+
+        let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        let worker = scheduler.get_workers(id).await.into_iter().next().unwrap();
+
+        assert_eq!(worker.node_id.as_deref(), Some("node-a"));
+        assert_eq!(worker.endpoint.host, "10.0.0.5");
+    }
+
+    #[tokio::test]
+    async fn test_deleting_deployment_frees_node_port_for_reuse() {
+        let scheduler = Scheduler::ephemeral(30000, 1).await;
+        let node =
+            crate::layout::NodeDescriptor::new("node-a", "zone-1", 1).with_address("10.0.0.5");
+        scheduler.register_node(node, test_gpu_info(1)).await;
+
+        let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        scheduler.delete_deployment(id, true).await.unwrap();
+
+        // With only one port in range, a second deployment only succeeds if
+        // the first one's port was released back into node-a's pool.
+        let spec = DeploymentSpec::new("test2".to_string(), "model".to_string());
+        assert!(scheduler.create_deployment(spec).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_node_falls_back_to_local_placement() {
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
+        let spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        let worker = scheduler.get_workers(id).await.into_iter().next().unwrap();
+
+        assert!(worker.node_id.is_none());
+    }
+
+    /// A GPU-aware strategy may pick a GPU other than the lowest-indexed
+    /// available one. The allocator must reserve exactly that GPU rather than
+    /// the first-N-by-index, or the real reservation and the worker's
+    /// reported `gpu_ids` diverge.
+    #[tokio::test]
+    async fn test_bin_pack_strategy_reserves_the_chosen_gpu() {
+        let gb = 1024u64 * 1024 * 1024;
+        let gpu_info = dmrlet_core::GpuInfo {
+            total_count: 3,
+            available_count: 3,
+            devices: vec![
+                dmrlet_core::GpuDevice {
+                    index: 0,
+                    name: "GPU 0".to_string(),
+                    memory_total: 16 * gb,
+                    memory_free: 16 * gb,
+                    vendor: dmrlet_core::GpuVendor::Nvidia,
+                    available: true,
+                    utilization: Some(0),
+                    pci: None,
+                    temperature_c: None,
+                    power_usage_mw: None,
+                    power_limit_mw: None,
+                    processes: Vec::new(),
+                },
+                dmrlet_core::GpuDevice {
+                    index: 1,
+                    name: "GPU 1".to_string(),
+                    memory_total: 6 * gb,
+                    memory_free: 6 * gb,
+                    vendor: dmrlet_core::GpuVendor::Nvidia,
+                    available: true,
+                    utilization: Some(0),
+                    pci: None,
+                    temperature_c: None,
+                    power_usage_mw: None,
+                    power_limit_mw: None,
+                    processes: Vec::new(),
+                },
+                dmrlet_core::GpuDevice {
+                    index: 2,
+                    name: "GPU 2".to_string(),
+                    memory_total: 2 * gb,
+                    memory_free: 2 * gb,
+                    vendor: dmrlet_core::GpuVendor::Nvidia,
+                    available: true,
+                    utilization: Some(0),
+                    pci: None,
+                    temperature_c: None,
+                    power_usage_mw: None,
+                    power_limit_mw: None,
+                    processes: Vec::new(),
+                },
+            ],
+        };
+
+        let scheduler = Scheduler::ephemeral(30000, 100).await;
+        let node = crate::layout::NodeDescriptor::new("node-a", "zone-1", 1);
+        scheduler.register_node(node, gpu_info).await;
+
+        let mut spec = DeploymentSpec::new("test".to_string(), "model".to_string());
+        spec.resources.gpu_count = 1;
+        spec.resources.memory = Some("4Gi".to_string());
+        spec.placement_strategy = dmrlet_core::PlacementStrategyKind::BinPack;
+
+        let id = scheduler.create_deployment(spec).await.unwrap();
+        let worker = scheduler.get_workers(id).await.into_iter().next().unwrap();
+        // GPU 1 is the least-free device that still fits 4Gi; GPU 0 must stay
+        // untouched even though it's the lowest index.
+        assert_eq!(worker.gpu_ids, vec![1]);
+        assert_eq!(worker.gpu_reservation_ids.len(), 1);
+
+        let pools = scheduler.node_pools.read().await;
+        let status = pools.get("node-a").unwrap().gpu_allocator.get_gpu_info();
+        assert!(!status[0].allocated);
+        assert!(status[1].allocated);
+        assert_eq!(status[1].reserved_bytes, 4 * gb);
+        assert!(!status[2].allocated);
+
+        drop(pools);
+        scheduler.delete_deployment(id, true).await.unwrap();
+
+        // Releasing the worker must free exactly the GPU and memory it
+        // actually held.
+        let pools = scheduler.node_pools.read().await;
+        let status = pools.get("node-a").unwrap().gpu_allocator.get_gpu_info();
+        assert!(status.iter().all(|s| !s.allocated));
+        assert!(status.iter().all(|s| s.reserved_bytes == 0));
+    }
 }
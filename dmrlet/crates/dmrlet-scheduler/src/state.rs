@@ -0,0 +1,223 @@
+//! Durable state persistence for the scheduler.
+//!
+//! The control plane keeps its placement state (deployments and workers) in a
+//! [`StateStore`] so a crash or redeploy can replay it and rebuild the
+//! in-memory maps instead of orphaning running workers. The default
+//! implementation is SQL-backed, modeled on a `job_queue`-style table; a
+//! trivial in-memory store is also provided for tests and single-shot runs.
+
+use async_trait::async_trait;
+use dmrlet_core::{DeploymentSpec, DmrletError, DmrletResult, Worker};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// Persistent store for scheduler placement state.
+#[async_trait]
+pub trait StateStore: Send + Sync {
+    /// Load all persisted deployment specs.
+    async fn load_deployments(&self) -> DmrletResult<Vec<DeploymentSpec>>;
+    /// Persist (insert or update) a deployment spec.
+    async fn save_deployment(&self, spec: &DeploymentSpec) -> DmrletResult<()>;
+    /// Delete a deployment and forget its spec.
+    async fn delete_deployment(&self, id: Uuid) -> DmrletResult<()>;
+    /// Load all persisted workers.
+    async fn load_workers(&self) -> DmrletResult<Vec<Worker>>;
+    /// Persist (insert or update) a worker.
+    async fn save_worker(&self, worker: &Worker) -> DmrletResult<()>;
+    /// Remove a worker from the store.
+    async fn remove_worker(&self, id: Uuid) -> DmrletResult<()>;
+}
+
+/// In-memory [`StateStore`], used for tests and ephemeral single-node runs.
+#[derive(Default)]
+pub struct MemoryStateStore {
+    deployments: Mutex<HashMap<Uuid, DeploymentSpec>>,
+    workers: Mutex<HashMap<Uuid, Worker>>,
+}
+
+impl MemoryStateStore {
+    /// Create an empty in-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StateStore for MemoryStateStore {
+    async fn load_deployments(&self) -> DmrletResult<Vec<DeploymentSpec>> {
+        Ok(self.deployments.lock().await.values().cloned().collect())
+    }
+
+    async fn save_deployment(&self, spec: &DeploymentSpec) -> DmrletResult<()> {
+        self.deployments.lock().await.insert(spec.id, spec.clone());
+        Ok(())
+    }
+
+    async fn delete_deployment(&self, id: Uuid) -> DmrletResult<()> {
+        self.deployments.lock().await.remove(&id);
+        Ok(())
+    }
+
+    async fn load_workers(&self) -> DmrletResult<Vec<Worker>> {
+        Ok(self.workers.lock().await.values().cloned().collect())
+    }
+
+    async fn save_worker(&self, worker: &Worker) -> DmrletResult<()> {
+        self.workers.lock().await.insert(worker.id, worker.clone());
+        Ok(())
+    }
+
+    async fn remove_worker(&self, id: Uuid) -> DmrletResult<()> {
+        self.workers.lock().await.remove(&id);
+        Ok(())
+    }
+}
+
+/// SQL-backed [`StateStore`] persisting into a single `scheduler_state` table.
+///
+/// The schema mirrors a `job_queue` row: a UUID primary key, a `kind`
+/// discriminator (`deployment` / `worker`), the serialized JSON payload, a
+/// coarse `status`, and an `updated_at` timestamp for observability.
+pub struct SqlStateStore {
+    pool: sqlx::PgPool,
+}
+
+impl SqlStateStore {
+    /// Connect and ensure the backing table exists.
+    pub async fn connect(database_url: &str) -> DmrletResult<Self> {
+        let pool = sqlx::PgPool::connect(database_url)
+            .await
+            .map_err(|e| DmrletError::Config(format!("state store connect failed: {}", e)))?;
+        let store = Self { pool };
+        store.migrate().await?;
+        Ok(store)
+    }
+
+    /// Build a store from an existing connection pool.
+    pub fn with_pool(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create the `scheduler_state` table if it is not already present.
+    pub async fn migrate(&self) -> DmrletResult<()> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS scheduler_state (
+                id UUID PRIMARY KEY,
+                kind TEXT NOT NULL,
+                spec JSONB NOT NULL,
+                status TEXT NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(map_sql)?;
+        Ok(())
+    }
+
+    /// Load and deserialize every payload of a given kind.
+    async fn load_kind<T: serde::de::DeserializeOwned>(&self, kind: &str) -> DmrletResult<Vec<T>> {
+        let rows: Vec<(serde_json::Value,)> =
+            sqlx::query_as("SELECT spec FROM scheduler_state WHERE kind = $1")
+                .bind(kind)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(map_sql)?;
+        rows.into_iter()
+            .map(|(value,)| serde_json::from_value(value).map_err(map_json))
+            .collect()
+    }
+
+    /// Upsert one payload row.
+    async fn upsert<T: serde::Serialize>(
+        &self,
+        id: Uuid,
+        kind: &str,
+        status: &str,
+        payload: &T,
+    ) -> DmrletResult<()> {
+        let spec = serde_json::to_value(payload).map_err(map_json)?;
+        sqlx::query(
+            "INSERT INTO scheduler_state (id, kind, spec, status, updated_at)
+             VALUES ($1, $2, $3, $4, now())
+             ON CONFLICT (id) DO UPDATE
+             SET spec = EXCLUDED.spec, status = EXCLUDED.status, updated_at = now()",
+        )
+        .bind(id)
+        .bind(kind)
+        .bind(spec)
+        .bind(status)
+        .execute(&self.pool)
+        .await
+        .map_err(map_sql)?;
+        Ok(())
+    }
+
+    /// Delete one row by id.
+    async fn delete(&self, id: Uuid) -> DmrletResult<()> {
+        sqlx::query("DELETE FROM scheduler_state WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(map_sql)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StateStore for SqlStateStore {
+    async fn load_deployments(&self) -> DmrletResult<Vec<DeploymentSpec>> {
+        self.load_kind("deployment").await
+    }
+
+    async fn save_deployment(&self, spec: &DeploymentSpec) -> DmrletResult<()> {
+        self.upsert(spec.id, "deployment", "active", spec).await
+    }
+
+    async fn delete_deployment(&self, id: Uuid) -> DmrletResult<()> {
+        self.delete(id).await
+    }
+
+    async fn load_workers(&self) -> DmrletResult<Vec<Worker>> {
+        self.load_kind("worker").await
+    }
+
+    async fn save_worker(&self, worker: &Worker) -> DmrletResult<()> {
+        self.upsert(worker.id, "worker", &worker.status.to_string(), worker)
+            .await
+    }
+
+    async fn remove_worker(&self, id: Uuid) -> DmrletResult<()> {
+        self.delete(id).await
+    }
+}
+
+/// Map a SQL error into a `DmrletError`.
+fn map_sql(e: sqlx::Error) -> DmrletError {
+    DmrletError::Config(format!("state store query failed: {}", e))
+}
+
+/// Map a (de)serialization error into a `DmrletError`.
+fn map_json(e: serde_json::Error) -> DmrletError {
+    DmrletError::Config(format!("state store payload error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store_roundtrip() {
+        let store = MemoryStateStore::new();
+        let spec = DeploymentSpec::new("t".to_string(), "m".to_string());
+        store.save_deployment(&spec).await.unwrap();
+
+        let loaded = store.load_deployments().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, spec.id);
+
+        store.delete_deployment(spec.id).await.unwrap();
+        assert!(store.load_deployments().await.unwrap().is_empty());
+    }
+}
@@ -1,10 +1,14 @@
 //! Local model cache
 
-use dmrlet_core::DmrletResult;
+use crate::metadata::{CacheStore, MemoryCacheStore};
+use dmrlet_core::{DmrletError, DmrletResult};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
-use std::time::SystemTime;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::io::AsyncReadExt;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
@@ -21,6 +25,97 @@ pub struct CachedModel {
     pub last_accessed: SystemTime,
     /// Download time
     pub downloaded_at: SystemTime,
+    /// Content digest (`<algo>:<hex>`, `sha256` or `blake3`) of the blob this
+    /// reference resolves to. Several references may share the same digest,
+    /// in which case they share the same on-disk blob (see
+    /// [`ModelCache::blob_path`]).
+    #[serde(default)]
+    pub digest: String,
+}
+
+/// Content-hash algorithms [`ModelCache::add`] can verify a download against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Sha256,
+    Blake3,
+}
+
+impl DigestAlgorithm {
+    /// Determine the algorithm from the prefix of a `<algo>:<hex>` digest.
+    fn parse(digest: &str) -> DmrletResult<Self> {
+        match digest.split_once(':').map(|(algo, _)| algo) {
+            Some("sha256") => Ok(Self::Sha256),
+            Some("blake3") => Ok(Self::Blake3),
+            _ => Err(DmrletError::Storage(format!(
+                "Unsupported or malformed digest: {}",
+                digest
+            ))),
+        }
+    }
+
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        }
+    }
+}
+
+/// A stored blob, named after the digest of its content. Several
+/// [`CachedModel`] references may point at the same blob, so it is written
+/// to disk only once no matter how many references resolve to it.
+#[derive(Debug, Clone)]
+struct Blob {
+    /// Content-addressed path (`<base_path>/<algo>/<hex>`).
+    path: PathBuf,
+    /// Size in bytes.
+    size: u64,
+    /// Most recent access through any reference pointing at this blob.
+    last_accessed: SystemTime,
+}
+
+/// A recorded eviction, surfaced through the status API.
+#[derive(Debug, Clone, Serialize)]
+pub struct EvictionEvent {
+    /// Reference of the model that was evicted.
+    pub reference: String,
+    /// Bytes reclaimed by the eviction.
+    pub freed: u64,
+    /// When the eviction happened.
+    pub evicted_at: SystemTime,
+}
+
+/// Idle/absolute expiry rules applied by [`ModelCache::sweep_expired`],
+/// checked on its own schedule independent of the size-bounded LRU eviction
+/// in [`ModelCache::ensure_space`]. Adapted from the object-lifecycle-rule
+/// idea in Garage's S3 `lifecycle.rs`, scaled down to a single global rule
+/// plus per-reference overrides.
+#[derive(Debug, Clone, Default)]
+pub struct ExpiryPolicy {
+    /// Evict a reference once `now - last_accessed` exceeds this, unless
+    /// `max_idle_overrides` has a different limit for it.
+    pub max_idle: Option<Duration>,
+    /// Evict a reference once `now - downloaded_at` exceeds this, regardless
+    /// of how recently it was used.
+    pub max_ttl: Option<Duration>,
+    /// Per-reference overrides for `max_idle`.
+    pub max_idle_overrides: HashMap<String, Duration>,
+}
+
+/// Outcome of a [`ModelCache::scrub`] pass.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScrubReport {
+    /// References dropped from the index because their blob was missing,
+    /// size-mismatched, or failed re-hashing; these will re-download on next
+    /// use.
+    pub repaired: Vec<String>,
+    /// Bytes reclaimed by dropping entries in `repaired`.
+    pub bytes_reclaimed: u64,
+    /// Files found under `base_path` with no matching index entry, and
+    /// deleted.
+    pub orphans_removed: Vec<PathBuf>,
+    /// Bytes reclaimed by deleting `orphans_removed`.
+    pub orphan_bytes_reclaimed: u64,
 }
 
 /// Model cache manager
@@ -33,30 +128,241 @@ pub struct ModelCache {
     current_size: RwLock<u64>,
     /// Cached models indexed by reference
     models: RwLock<HashMap<String, CachedModel>>,
+    /// Stored blobs indexed by content digest. This is the unit LRU eviction
+    /// actually acts on, since several references can dedupe onto one blob.
+    blobs: RwLock<HashMap<String, Blob>>,
+    /// Outstanding references per model, keyed by reference. A model with a
+    /// non-zero count is pinned by a running worker and never evicted.
+    references: RwLock<HashMap<String, usize>>,
+    /// Bounded log of recent eviction events.
+    evictions: RwLock<Vec<EvictionEvent>>,
     /// Enable LRU eviction
     lru_enabled: bool,
+    /// Idle/TTL expiry rules applied by `sweep_expired`, independent of LRU
+    expiry: RwLock<ExpiryPolicy>,
+    /// Durable backing store for cache metadata, replayed by `init`
+    store: Arc<dyn CacheStore>,
 }
 
+/// Maximum number of eviction events retained for status reporting.
+const MAX_EVICTION_EVENTS: usize = 64;
+
 impl ModelCache {
-    /// Create a new model cache
-    pub fn new(base_path: PathBuf, max_size: u64, lru_enabled: bool) -> Self {
+    /// Create a new model cache backed by `store`, which is replayed on
+    /// [`init`](Self::init) so cached models survive a daemon restart.
+    pub fn new(base_path: PathBuf, max_size: u64, lru_enabled: bool, store: Arc<dyn CacheStore>) -> Self {
         Self {
             base_path,
             max_size,
             current_size: RwLock::new(0),
             models: RwLock::new(HashMap::new()),
+            blobs: RwLock::new(HashMap::new()),
+            references: RwLock::new(HashMap::new()),
+            evictions: RwLock::new(Vec::new()),
             lru_enabled,
+            expiry: RwLock::new(ExpiryPolicy::default()),
+            store,
         }
     }
 
-    /// Initialize the cache by scanning existing models
+    /// Create a model cache with an in-memory metadata store, used for tests
+    /// and single-shot runs with no durability requirement.
+    pub fn ephemeral(base_path: PathBuf, max_size: u64, lru_enabled: bool) -> Self {
+        Self::new(base_path, max_size, lru_enabled, Arc::new(MemoryCacheStore::new()))
+    }
+
+    /// Configure idle/TTL expiry rules, checked independently of LRU by
+    /// [`sweep_expired`](Self::sweep_expired).
+    pub fn with_expiry_policy(mut self, policy: ExpiryPolicy) -> Self {
+        *self.expiry.get_mut() = policy;
+        self
+    }
+
+    /// Pin a model so it is never evicted while a worker is running on it.
+    ///
+    /// Each call increments the model's reference count; it is balanced by a
+    /// matching [`release`](Self::release).
+    pub async fn acquire(&self, reference: &str) {
+        let mut references = self.references.write().await;
+        *references.entry(reference.to_string()).or_insert(0) += 1;
+        debug!(reference = reference, "Pinned model in cache");
+    }
+
+    /// Release a reference previously taken with [`acquire`](Self::acquire).
+    pub async fn release(&self, reference: &str) {
+        let mut references = self.references.write().await;
+        if let Some(count) = references.get_mut(reference) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                references.remove(reference);
+            }
+        }
+        debug!(reference = reference, "Unpinned model in cache");
+    }
+
+    /// Whether a model is currently pinned by a running worker.
+    async fn is_referenced(&self, reference: &str) -> bool {
+        let references = self.references.read().await;
+        references.get(reference).copied().unwrap_or(0) > 0
+    }
+
+    /// Sum of pins across every reference that currently resolves to
+    /// `digest` (mirroring Garage's `block/rc.rs`). A non-zero count means a
+    /// running worker depends on this blob, so it must not be evicted even
+    /// if the particular reference that downloaded it is untouched.
+    async fn digest_refcount(&self, digest: &str) -> usize {
+        let models = self.models.read().await;
+        let references = self.references.read().await;
+        models
+            .values()
+            .filter(|m| m.digest == digest)
+            .map(|m| references.get(&m.reference).copied().unwrap_or(0))
+            .sum()
+    }
+
+    /// Content-addressed path for a blob digest (`<algo>/<hex>`), mirroring
+    /// [`crate::oci::OciStore`]'s layout.
+    fn blob_path(&self, digest: &str) -> PathBuf {
+        let (algo, hex) = digest.split_once(':').unwrap_or(("sha256", digest));
+        self.base_path.join(algo).join(hex)
+    }
+
+    /// Stream-hash a file with `algo`, returning its digest as `<algo>:<hex>`.
+    async fn hash_file(path: &Path, algo: DigestAlgorithm) -> DmrletResult<String> {
+        let mut file = tokio::fs::File::open(path).await?;
+        let mut buf = vec![0u8; 64 * 1024];
+
+        let hex_digest = match algo {
+            DigestAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                loop {
+                    let n = file.read(&mut buf).await?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+                hex(&hasher.finalize())
+            }
+            DigestAlgorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                loop {
+                    let n = file.read(&mut buf).await?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+                hasher.finalize().to_hex().to_string()
+            }
+        };
+
+        Ok(format!("{}:{}", algo.prefix(), hex_digest))
+    }
+
+    /// Initialize the cache by reconciling the metadata store against disk.
+    ///
+    /// Every persisted record is `stat`ed: records whose file is gone are
+    /// dropped, the rest are loaded into the in-memory index and grouped into
+    /// blobs by content digest (hashing now to backfill any record written
+    /// before digests existed). Any GGUF file found under `base_path` that
+    /// the store doesn't know about yet (e.g. left over from a crash
+    /// mid-download) is adopted as a new record. `current_size` is
+    /// recomputed from the surviving set of unique blobs so LRU accounting
+    /// is correct immediately after a restart.
     pub async fn init(&self) -> DmrletResult<()> {
         if !self.base_path.exists() {
             tokio::fs::create_dir_all(&self.base_path).await?;
             info!(path = %self.base_path.display(), "Created model cache directory");
         }
 
-        // Scan for existing models (would read metadata files in production)
+        let mut models = self.models.write().await;
+        let mut blobs = self.blobs.write().await;
+        let mut known_paths: HashSet<PathBuf> = HashSet::new();
+
+        for mut record in self.store.load_all().await? {
+            match tokio::fs::metadata(&record.path).await {
+                Ok(meta) => {
+                    known_paths.insert(record.path.clone());
+                    record.size = meta.len();
+
+                    if record.digest.is_empty() {
+                        record.digest = Self::hash_file(&record.path, DigestAlgorithm::Sha256).await.unwrap_or_default();
+                        self.store.put(&record).await?;
+                    }
+
+                    blobs
+                        .entry(record.digest.clone())
+                        .and_modify(|b| {
+                            if record.last_accessed > b.last_accessed {
+                                b.last_accessed = record.last_accessed;
+                            }
+                        })
+                        .or_insert_with(|| Blob {
+                            path: record.path.clone(),
+                            size: meta.len(),
+                            last_accessed: record.last_accessed,
+                        });
+
+                    let reference = record.reference.clone();
+                    models.insert(reference, record);
+                }
+                Err(_) => {
+                    warn!(
+                        reference = %record.reference,
+                        path = %record.path.display(),
+                        "Cached model file missing on disk; dropping stale record"
+                    );
+                    self.store.delete(&record.reference).await?;
+                }
+            }
+        }
+
+        let mut entries = tokio::fs::read_dir(&self.base_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if known_paths.contains(&path) || path.extension().and_then(|e| e.to_str()) != Some("gguf")
+            {
+                continue;
+            }
+
+            let meta = entry.metadata().await?;
+            let reference = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string();
+            let now = SystemTime::now();
+            let digest = Self::hash_file(&path, DigestAlgorithm::Sha256).await.unwrap_or_default();
+            let model = CachedModel {
+                reference: reference.clone(),
+                path: path.clone(),
+                size: meta.len(),
+                last_accessed: now,
+                downloaded_at: meta.modified().unwrap_or(now),
+                digest: digest.clone(),
+            };
+
+            self.store.put(&model).await?;
+            blobs.entry(digest).or_insert_with(|| Blob {
+                path,
+                size: meta.len(),
+                last_accessed: now,
+            });
+            info!(path = %model.path.display(), "Adopted orphaned model file found on disk");
+            models.insert(reference, model);
+        }
+
+        let total: u64 = blobs.values().map(|b| b.size).sum();
+        *self.current_size.write().await = total;
+
+        info!(
+            models = models.len(),
+            blobs = blobs.len(),
+            bytes = total,
+            "Model cache initialized from metadata store"
+        );
+
         Ok(())
     }
 
@@ -66,50 +372,138 @@ impl ModelCache {
         models.contains_key(reference)
     }
 
-    /// Get the path to a cached model
+    /// Get the path to a cached model, refreshing its LRU timestamp (and
+    /// that of the shared blob it resolves to).
     pub async fn get(&self, reference: &str) -> Option<PathBuf> {
-        let mut models = self.models.write().await;
-        if let Some(model) = models.get_mut(reference) {
+        let model = {
+            let mut models = self.models.write().await;
+            let Some(model) = models.get_mut(reference) else {
+                #[cfg(feature = "metrics")]
+                dmrlet_core::metrics::counter_inc("dmrlet_cache_misses_total", &[]);
+                return None;
+            };
             model.last_accessed = SystemTime::now();
-            Some(model.path.clone())
-        } else {
-            None
+            model.clone()
+        };
+
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::counter_inc("dmrlet_cache_hits_total", &[]);
+
+        if let Err(e) = self.store.put(&model).await {
+            warn!(reference = reference, error = %e, "Failed to persist updated last_accessed");
+        }
+
+        {
+            let mut blobs = self.blobs.write().await;
+            if let Some(blob) = blobs.get_mut(&model.digest) {
+                blob.last_accessed = model.last_accessed;
+            }
         }
+
+        Some(model.path)
     }
 
-    /// Add a model to the cache
+    /// Add a model to the cache.
+    ///
+    /// The file at `path` is hashed and moved into content-addressed storage
+    /// under its digest. If `expected_digest` (`<algo>:<hex>`, SHA-256 or
+    /// BLAKE3) is given, the computed hash must match it or the download is
+    /// treated as corrupt: the partial file is deleted and an error is
+    /// returned rather than caching a silently truncated model. If another
+    /// reference already holds the same content, the incoming file is
+    /// dropped in favor of the existing blob instead of storing a second
+    /// copy.
     pub async fn add(
         &self,
         reference: &str,
         path: PathBuf,
         size: u64,
+        expected_digest: Option<&str>,
     ) -> DmrletResult<()> {
-        // Check if we need to evict
-        if self.lru_enabled {
-            self.ensure_space(size).await?;
+        let algo = match expected_digest {
+            Some(digest) => DigestAlgorithm::parse(digest)?,
+            None => DigestAlgorithm::Sha256,
+        };
+        let digest = Self::hash_file(&path, algo).await?;
+
+        if let Some(expected) = expected_digest {
+            if digest != expected {
+                let _ = tokio::fs::remove_file(&path).await;
+                return Err(DmrletError::Storage(format!(
+                    "Digest mismatch for {}: expected {}, got {}",
+                    reference, expected, digest
+                )));
+            }
+        }
+
+        let blob_path = self.blob_path(&digest);
+
+        let deduped = {
+            let blobs = self.blobs.read().await;
+            blobs.contains_key(&digest)
+        };
+
+        if deduped {
+            if path != blob_path {
+                let _ = tokio::fs::remove_file(&path).await;
+            }
+            let mut blobs = self.blobs.write().await;
+            if let Some(blob) = blobs.get_mut(&digest) {
+                blob.last_accessed = SystemTime::now();
+            }
+        } else {
+            if self.lru_enabled {
+                self.ensure_space(size).await?;
+            }
+
+            if let Some(parent) = blob_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            if path != blob_path {
+                tokio::fs::rename(&path, &blob_path).await?;
+            }
+
+            let mut blobs = self.blobs.write().await;
+            blobs.insert(
+                digest.clone(),
+                Blob {
+                    path: blob_path.clone(),
+                    size,
+                    last_accessed: SystemTime::now(),
+                },
+            );
+            let mut current_size = self.current_size.write().await;
+            *current_size += size;
         }
 
         let model = CachedModel {
             reference: reference.to_string(),
-            path,
+            path: blob_path,
             size,
             last_accessed: SystemTime::now(),
             downloaded_at: SystemTime::now(),
+            digest: digest.clone(),
         };
 
-        let mut models = self.models.write().await;
-        let mut current_size = self.current_size.write().await;
+        self.store.put(&model).await?;
 
-        // Remove old entry if exists
-        if let Some(old) = models.remove(reference) {
-            *current_size = current_size.saturating_sub(old.size);
+        let old = {
+            let mut models = self.models.write().await;
+            models.insert(reference.to_string(), model)
+        };
+
+        if let Some(old) = old {
+            if old.digest != digest {
+                self.drop_unreferenced_blob(&old.digest).await?;
+            }
         }
 
-        *current_size += size;
-        models.insert(reference.to_string(), model);
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::counter_inc("dmrlet_cache_adds_total", &[]);
 
         debug!(
             reference = reference,
+            digest = %digest,
             size = size,
             "Added model to cache"
         );
@@ -119,85 +513,429 @@ impl ModelCache {
 
     /// Remove a model from the cache
     pub async fn remove(&self, reference: &str) -> DmrletResult<()> {
-        let mut models = self.models.write().await;
-        let mut current_size = self.current_size.write().await;
+        let old = {
+            let mut models = self.models.write().await;
+            models.remove(reference)
+        };
+
+        if let Some(model) = old {
+            self.store.delete(reference).await?;
+            self.drop_unreferenced_blob(&model.digest).await?;
+            info!(reference = reference, "Removed model from cache");
+        }
 
-        if let Some(model) = models.remove(reference) {
-            *current_size = current_size.saturating_sub(model.size);
+        Ok(())
+    }
 
-            // Delete the file
-            if model.path.exists() {
-                tokio::fs::remove_file(&model.path).await?;
-            }
+    /// Free `digest`'s on-disk blob once no reference in `models` points at
+    /// it anymore. A no-op if the digest is still in use or unknown.
+    async fn drop_unreferenced_blob(&self, digest: &str) -> DmrletResult<()> {
+        if digest.is_empty() {
+            return Ok(());
+        }
 
-            info!(reference = reference, "Removed model from cache");
+        let still_used = {
+            let models = self.models.read().await;
+            models.values().any(|m| m.digest == digest)
+        };
+        if still_used {
+            return Ok(());
+        }
+
+        let blob = {
+            let mut blobs = self.blobs.write().await;
+            blobs.remove(digest)
+        };
+
+        if let Some(blob) = blob {
+            if blob.path.exists() {
+                tokio::fs::remove_file(&blob.path).await?;
+            }
+            let mut current_size = self.current_size.write().await;
+            *current_size = current_size.saturating_sub(blob.size);
         }
 
         Ok(())
     }
 
+    /// Re-hash a cached model's on-disk blob and compare it against the
+    /// digest recorded when it was added, to catch bit-rot that silently
+    /// corrupts a file sometime after it was verified at download time.
+    ///
+    /// Returns `Ok(false)` (rather than an error) on mismatch, since a
+    /// failed check is an expected outcome operators poll for, not a
+    /// failure of the cache itself.
+    pub async fn verify(&self, reference: &str) -> DmrletResult<bool> {
+        let model = {
+            let models = self.models.read().await;
+            models
+                .get(reference)
+                .cloned()
+                .ok_or_else(|| DmrletError::ModelNotFound(reference.to_string()))?
+        };
+
+        let algo = DigestAlgorithm::parse(&model.digest)?;
+        let recomputed = Self::hash_file(&model.path, algo).await?;
+        Ok(recomputed == model.digest)
+    }
+
+    /// Scrub the cache: confirm every indexed blob is present, correctly
+    /// sized, and (when its digest can be parsed) still hashes to the value
+    /// it was stored under. Anything that fails is dropped from the index
+    /// and flagged for re-download rather than left to surface later as a
+    /// worker failing to start. Also walks `base_path`'s content-addressed
+    /// directories for files the index doesn't know about and reclaims
+    /// them.
+    ///
+    /// Mirrors Garage's periodic block scrub/resync.
+    pub async fn scrub(&self) -> DmrletResult<ScrubReport> {
+        let mut report = ScrubReport::default();
+
+        let digests: Vec<String> = {
+            let blobs = self.blobs.read().await;
+            blobs.keys().cloned().collect()
+        };
+
+        for digest in digests {
+            let blob = {
+                let blobs = self.blobs.read().await;
+                blobs.get(&digest).cloned()
+            };
+            let Some(blob) = blob else { continue };
+
+            let healthy = match tokio::fs::metadata(&blob.path).await {
+                Err(_) => false,
+                Ok(meta) if meta.len() != blob.size => false,
+                Ok(_) => match DigestAlgorithm::parse(&digest) {
+                    Ok(algo) => Self::hash_file(&blob.path, algo)
+                        .await
+                        .map(|h| h == digest)
+                        .unwrap_or(false),
+                    // No (or an unparseable) digest on record, e.g. a record
+                    // from before digests existed; presence and size are all
+                    // that can be checked.
+                    Err(_) => true,
+                },
+            };
+
+            if healthy {
+                continue;
+            }
+
+            warn!(
+                digest = %digest,
+                path = %blob.path.display(),
+                "Scrub found a missing or corrupt blob; dropping for re-download"
+            );
+
+            let affected: Vec<String> = {
+                let models = self.models.read().await;
+                models
+                    .values()
+                    .filter(|m| m.digest == digest)
+                    .map(|m| m.reference.clone())
+                    .collect()
+            };
+
+            for reference in &affected {
+                {
+                    let mut models = self.models.write().await;
+                    models.remove(reference);
+                }
+                self.store.delete(reference).await?;
+                report.repaired.push(reference.clone());
+            }
+
+            {
+                let mut blobs = self.blobs.write().await;
+                blobs.remove(&digest);
+            }
+            let _ = tokio::fs::remove_file(&blob.path).await;
+
+            let mut current_size = self.current_size.write().await;
+            *current_size = current_size.saturating_sub(blob.size);
+            report.bytes_reclaimed += blob.size;
+        }
+
+        // Walk the content-addressed `<algo>/<hex>` layout for files the
+        // index no longer references (e.g. left behind by a crash mid-write
+        // or an eviction that removed the index entry but not the file).
+        let known_paths: HashSet<PathBuf> = {
+            let blobs = self.blobs.read().await;
+            blobs.values().map(|b| b.path.clone()).collect()
+        };
+
+        if let Ok(mut algo_dirs) = tokio::fs::read_dir(&self.base_path).await {
+            while let Ok(Some(dir_entry)) = algo_dirs.next_entry().await {
+                let is_dir = dir_entry.file_type().await.map(|t| t.is_dir()).unwrap_or(false);
+                if !is_dir {
+                    continue;
+                }
+
+                let Ok(mut files) = tokio::fs::read_dir(dir_entry.path()).await else {
+                    continue;
+                };
+                while let Ok(Some(file_entry)) = files.next_entry().await {
+                    let path = file_entry.path();
+                    if known_paths.contains(&path) {
+                        continue;
+                    }
+                    let Ok(meta) = file_entry.metadata().await else {
+                        continue;
+                    };
+                    if meta.is_file() && tokio::fs::remove_file(&path).await.is_ok() {
+                        report.orphan_bytes_reclaimed += meta.len();
+                        report.orphans_removed.push(path);
+                    }
+                }
+            }
+        }
+
+        info!(
+            repaired = report.repaired.len(),
+            bytes_reclaimed = report.bytes_reclaimed,
+            orphans = report.orphans_removed.len(),
+            orphan_bytes_reclaimed = report.orphan_bytes_reclaimed,
+            "Cache scrub complete"
+        );
+
+        Ok(report)
+    }
+
+    /// Evict every unreferenced model whose idle time or absolute age
+    /// exceeds the configured [`ExpiryPolicy`]. Runs independently of (and on
+    /// its own schedule from) the size-bounded LRU eviction in
+    /// [`ensure_space`](Self::ensure_space); a model pinned by a running
+    /// worker is never expired regardless of age.
+    ///
+    /// Returns the references that were expired.
+    pub async fn sweep_expired(&self) -> DmrletResult<Vec<String>> {
+        let policy = self.expiry.read().await.clone();
+        if policy.max_idle.is_none() && policy.max_ttl.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let now = SystemTime::now();
+        let candidates: Vec<String> = {
+            let models = self.models.read().await;
+            models
+                .values()
+                .filter(|m| {
+                    let idle_limit = policy
+                        .max_idle_overrides
+                        .get(&m.reference)
+                        .copied()
+                        .or(policy.max_idle);
+                    let idle_expired = idle_limit
+                        .is_some_and(|limit| now.duration_since(m.last_accessed).unwrap_or_default() > limit);
+                    let ttl_expired = policy
+                        .max_ttl
+                        .is_some_and(|limit| now.duration_since(m.downloaded_at).unwrap_or_default() > limit);
+                    idle_expired || ttl_expired
+                })
+                .map(|m| m.reference.clone())
+                .collect()
+        };
+
+        let mut expired = Vec::new();
+        for reference in candidates {
+            if self.is_referenced(&reference).await {
+                continue;
+            }
+            self.remove(&reference).await?;
+            debug!(reference = %reference, "Expired idle/TTL-exceeded model from cache");
+            expired.push(reference);
+        }
+
+        if !expired.is_empty() {
+            info!(count = expired.len(), "Expiry sweep removed models from cache");
+        }
+
+        Ok(expired)
+    }
+
+    /// Spawn a background task that runs [`sweep_expired`](Self::sweep_expired)
+    /// on a fixed interval for as long as the cache is alive.
+    pub fn spawn_expiry_sweeper(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.sweep_expired().await {
+                    warn!(error = %e, "Expiry sweep failed");
+                }
+            }
+        })
+    }
+
     /// List all cached models
     pub async fn list(&self) -> Vec<CachedModel> {
         let models = self.models.read().await;
         models.values().cloned().collect()
     }
 
-    /// Ensure there's enough space for a new model
+    /// Ensure there's enough space for a new model.
+    ///
+    /// Evicts least-recently-used blobs that no reference currently pins
+    /// ([`digest_refcount`](Self::digest_refcount) is zero) until the
+    /// incoming model fits. A model whose size alone exceeds the configured
+    /// budget can never fit and yields a `ResourceExhausted` error, as does
+    /// the case where every remaining blob is still in use.
     async fn ensure_space(&self, needed: u64) -> DmrletResult<()> {
+        if needed > self.max_size {
+            return Err(DmrletError::ResourceExhausted(format!(
+                "Model of {} bytes exceeds cache budget of {} bytes",
+                needed, self.max_size
+            )));
+        }
+
         let current_size = *self.current_size.read().await;
 
         if current_size + needed <= self.max_size {
             return Ok(());
         }
 
-        // Need to evict models
         let to_free = (current_size + needed).saturating_sub(self.max_size);
 
-        // Clone the models we need to consider for eviction
-        let models_to_consider: Vec<CachedModel> = {
-            let models = self.models.read().await;
-            models.values().cloned().collect()
+        // Consider only unreferenced blobs, least-recently-used first.
+        let mut blobs_by_access: Vec<(String, Blob)> = {
+            let blobs = self.blobs.read().await;
+            blobs.iter().map(|(digest, blob)| (digest.clone(), blob.clone())).collect()
         };
-
-        let mut models_by_access = models_to_consider;
-        models_by_access.sort_by(|a, b| a.last_accessed.cmp(&b.last_accessed));
+        blobs_by_access.sort_by(|a, b| a.1.last_accessed.cmp(&b.1.last_accessed));
 
         let mut freed = 0u64;
-        let mut to_remove = Vec::new();
+        let mut to_evict = Vec::new();
 
-        for model in models_by_access {
+        for (digest, blob) in blobs_by_access {
             if freed >= to_free {
                 break;
             }
-            to_remove.push(model.reference.clone());
-            freed += model.size;
+            if self.digest_refcount(&digest).await > 0 {
+                continue;
+            }
+            freed += blob.size;
+            to_evict.push((digest, blob.size));
         }
 
-        for reference in to_remove {
-            warn!(
-                reference = %reference,
-                "Evicting model from cache (LRU)"
-            );
-            self.remove(&reference).await?;
+        if freed < to_free {
+            return Err(DmrletError::ResourceExhausted(format!(
+                "Cannot free {} bytes for new model: {} bytes pinned by running workers",
+                to_free,
+                to_free.saturating_sub(freed)
+            )));
+        }
+
+        for (digest, size) in to_evict {
+            self.evict_blob(&digest, size).await?;
         }
 
         Ok(())
     }
 
+    /// Evict every reference resolving to `digest`, then remove its blob.
+    async fn evict_blob(&self, digest: &str, size: u64) -> DmrletResult<()> {
+        let affected: Vec<String> = {
+            let models = self.models.read().await;
+            models
+                .values()
+                .filter(|m| m.digest == digest)
+                .map(|m| m.reference.clone())
+                .collect()
+        };
+
+        for reference in &affected {
+            warn!(reference = %reference, digest = %digest, "Evicting model from cache (LRU)");
+            {
+                let mut models = self.models.write().await;
+                models.remove(reference);
+            }
+            self.store.delete(reference).await?;
+            self.record_eviction(reference, size).await;
+        }
+
+        {
+            let mut blobs = self.blobs.write().await;
+            blobs.remove(digest);
+        }
+
+        let blob_path = self.blob_path(digest);
+        if blob_path.exists() {
+            tokio::fs::remove_file(&blob_path).await?;
+        }
+
+        let mut current_size = self.current_size.write().await;
+        *current_size = current_size.saturating_sub(size);
+
+        Ok(())
+    }
+
+    /// Append an eviction to the bounded event log.
+    async fn record_eviction(&self, reference: &str, freed: u64) {
+        #[cfg(feature = "metrics")]
+        dmrlet_core::metrics::counter_inc("dmrlet_cache_evictions_total", &[]);
+
+        let mut events = self.evictions.write().await;
+        events.push(EvictionEvent {
+            reference: reference.to_string(),
+            freed,
+            evicted_at: SystemTime::now(),
+        });
+        let overflow = events.len().saturating_sub(MAX_EVICTION_EVENTS);
+        if overflow > 0 {
+            events.drain(0..overflow);
+        }
+    }
+
+    /// Recent eviction events, oldest first.
+    pub async fn evictions(&self) -> Vec<EvictionEvent> {
+        self.evictions.read().await.clone()
+    }
+
     /// Get cache statistics
     pub async fn stats(&self) -> CacheStats {
         let current_size = *self.current_size.read().await;
         let models = self.models.read().await;
+        let policy = self.expiry.read().await.clone();
+        let references = self.references.read().await;
+
+        let next_expiry = models
+            .values()
+            .filter(|m| references.get(&m.reference).copied().unwrap_or(0) == 0)
+            .filter_map(|m| {
+                let idle_limit = policy
+                    .max_idle_overrides
+                    .get(&m.reference)
+                    .copied()
+                    .or(policy.max_idle)
+                    .map(|limit| m.last_accessed + limit);
+                let ttl_limit = policy.max_ttl.map(|limit| m.downloaded_at + limit);
+                match (idle_limit, ttl_limit) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (Some(a), None) => Some(a),
+                    (None, Some(b)) => Some(b),
+                    (None, None) => None,
+                }
+            })
+            .min();
 
         CacheStats {
             total_size: current_size,
             max_size: self.max_size,
             model_count: models.len(),
             utilization: (current_size as f64 / self.max_size as f64) * 100.0,
+            max_idle_secs: policy.max_idle.map(|d| d.as_secs()),
+            max_ttl_secs: policy.max_ttl.map(|d| d.as_secs()),
+            next_expiry,
         }
     }
 }
 
+/// Hex-encode a byte slice.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 /// Cache statistics
 #[derive(Debug, Clone, Serialize)]
 pub struct CacheStats {
@@ -209,46 +947,443 @@ pub struct CacheStats {
     pub model_count: usize,
     /// Cache utilization percentage
     pub utilization: f64,
+    /// Configured idle timeout, in seconds, if expiry is enabled.
+    pub max_idle_secs: Option<u64>,
+    /// Configured absolute TTL, in seconds, if expiry is enabled.
+    pub max_ttl_secs: Option<u64>,
+    /// Soonest time an unreferenced cached model will become eligible for
+    /// expiry under the current policy, if any model is subject to one.
+    pub next_expiry: Option<SystemTime>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Fresh scratch directory for a test, cleaned up by the caller.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dmrlet-cache-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Write a source file with real content so `add`'s hashing has
+    /// something to read.
+    fn write_source(dir: &Path, name: &str, content: &[u8]) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
     #[tokio::test]
     async fn test_model_cache() {
-        let cache = ModelCache::new(
-            PathBuf::from("/tmp/test-cache"),
-            1024 * 1024,
-            false,
-        );
+        let dir = test_dir("basic");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
 
         assert!(!cache.has("test-model").await);
 
-        cache
-            .add("test-model", PathBuf::from("/tmp/model.gguf"), 1024)
-            .await
-            .unwrap();
+        let source = write_source(&dir, "model.gguf", b"hello world");
+        cache.add("test-model", source, 11, None).await.unwrap();
 
         assert!(cache.has("test-model").await);
         assert!(cache.get("test-model").await.is_some());
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
     #[tokio::test]
     async fn test_cache_stats() {
-        let cache = ModelCache::new(PathBuf::from("/tmp/test"), 1024 * 1024, false);
+        let dir = test_dir("stats");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
 
-        cache
-            .add("model1", PathBuf::from("/tmp/m1.gguf"), 512)
-            .await
-            .unwrap();
-        cache
-            .add("model2", PathBuf::from("/tmp/m2.gguf"), 256)
-            .await
-            .unwrap();
+        let m1 = write_source(&dir, "m1.gguf", &[1u8; 512]);
+        let m2 = write_source(&dir, "m2.gguf", &[2u8; 256]);
+        cache.add("model1", m1, 512, None).await.unwrap();
+        cache.add("model2", m2, 256, None).await.unwrap();
 
         let stats = cache.stats().await;
         assert_eq!(stats.model_count, 2);
         assert_eq!(stats.total_size, 768);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lru_eviction_records_event() {
+        let dir = test_dir("evict");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1000, true);
+
+        let old = write_source(&dir, "old.gguf", &[1u8; 600]);
+        cache.add("old", old, 600, None).await.unwrap();
+        // Pushes over budget; "old" is unreferenced and gets evicted.
+        let new = write_source(&dir, "new.gguf", &[2u8; 600]);
+        cache.add("new", new, 600, None).await.unwrap();
+
+        assert!(!cache.has("old").await);
+        assert!(cache.has("new").await);
+        assert_eq!(cache.evictions().await.len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_referenced_model_is_pinned() {
+        let dir = test_dir("pin");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1000, true);
+
+        let pinned = write_source(&dir, "pinned.gguf", &[1u8; 600]);
+        cache.add("pinned", pinned, 600, None).await.unwrap();
+        cache.acquire("pinned").await;
+
+        // The only eviction candidate is pinned, so there is no room.
+        let incoming = write_source(&dir, "incoming.gguf", &[2u8; 600]);
+        let result = cache.add("incoming", incoming, 600, None).await;
+        assert!(matches!(result, Err(DmrletError::ResourceExhausted(_))));
+        assert!(cache.has("pinned").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oversized_model_rejected() {
+        let dir = test_dir("big");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1000, true);
+
+        let huge = write_source(&dir, "huge.gguf", &[0u8; 2000]);
+        let result = cache.add("huge", huge, 2000, None).await;
+        assert!(matches!(result, Err(DmrletError::ResourceExhausted(_))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dedup_shares_one_blob() {
+        let dir = test_dir("dedup");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let a = write_source(&dir, "a.gguf", b"identical content");
+        let b = write_source(&dir, "b.gguf", b"identical content");
+        cache.add("model-a", a, 17, None).await.unwrap();
+        cache.add("model-b", b, 17, None).await.unwrap();
+
+        // Same bytes dedupe onto a single blob, so total_size counts it once.
+        let stats = cache.stats().await;
+        assert_eq!(stats.total_size, 17);
+        assert_eq!(stats.model_count, 2);
+
+        let path_a = cache.get("model-a").await.unwrap();
+        let path_b = cache.get("model-b").await.unwrap();
+        assert_eq!(path_a, path_b);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shared_blob_survives_eviction_while_one_alias_pinned() {
+        let dir = test_dir("shared-pin");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1000, true);
+
+        let a = write_source(&dir, "a.gguf", &[9u8; 600]);
+        let b = write_source(&dir, "b.gguf", &[9u8; 600]);
+        cache.add("alias-a", a, 600, None).await.unwrap();
+        cache.add("alias-b", b, 600, None).await.unwrap();
+        cache.acquire("alias-a").await;
+
+        // Both aliases resolve to the same digest, pinned through
+        // "alias-a"; a third model can't find evictable space.
+        let incoming = write_source(&dir, "incoming.gguf", &[1u8; 600]);
+        let result = cache.add("incoming", incoming, 600, None).await;
+        assert!(matches!(result, Err(DmrletError::ResourceExhausted(_))));
+        assert!(cache.has("alias-a").await);
+        assert!(cache.has("alias-b").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_init_reconciles_metadata_store_against_disk() {
+        let base = test_dir("init-reconcile");
+
+        // A GGUF file with no metadata record should be adopted...
+        std::fs::write(base.join("orphan.gguf"), b"0123456789").unwrap();
+
+        let store = Arc::new(MemoryCacheStore::new());
+
+        // ...while a stale record whose file is gone should be dropped.
+        store
+            .put(&CachedModel {
+                reference: "gone".to_string(),
+                path: base.join("gone.gguf"),
+                size: 999,
+                last_accessed: SystemTime::now(),
+                downloaded_at: SystemTime::now(),
+                digest: "sha256:dead".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let cache = ModelCache::new(base.clone(), 1024 * 1024, false, store);
+        cache.init().await.unwrap();
+
+        assert!(!cache.has("gone").await);
+        assert!(cache.has("orphan").await);
+        assert_eq!(cache.stats().await.total_size, 10);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_init_survives_restart() {
+        let base = test_dir("init-restart");
+        std::fs::write(base.join("model.gguf"), b"0123456789").unwrap();
+
+        let store: Arc<dyn CacheStore> = Arc::new(MemoryCacheStore::new());
+        let first = ModelCache::new(base.clone(), 1024 * 1024, false, Arc::clone(&store));
+        first.init().await.unwrap();
+        first.get("model").await.unwrap();
+
+        // A fresh cache over the same store, as after a daemon restart,
+        // should pick up the model from its persisted record rather than
+        // adopting it again as an orphan.
+        let second = ModelCache::new(base.clone(), 1024 * 1024, false, store);
+        second.init().await.unwrap();
+        assert!(second.has("model").await);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_accepts_matching_expected_digest() {
+        let dir = test_dir("digest-match");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"hello world");
+        let expected = format!("sha256:{}", hex(&Sha256::digest(b"hello world")));
+        cache.add("test-model", source, 11, Some(&expected)).await.unwrap();
+
+        assert!(cache.has("test-model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_rejects_and_deletes_on_digest_mismatch() {
+        let dir = test_dir("digest-mismatch");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"corrupted");
+        let result = cache
+            .add("test-model", source.clone(), 9, Some("sha256:0000000000000000000000000000000000000000000000000000000000000000"))
+            .await;
+
+        assert!(result.is_err());
+        assert!(!cache.has("test-model").await);
+        assert!(!source.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_accepts_blake3_expected_digest() {
+        let dir = test_dir("digest-blake3");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"hello world");
+        let expected = format!("blake3:{}", blake3::hash(b"hello world").to_hex());
+        cache.add("test-model", source, 11, Some(&expected)).await.unwrap();
+
+        assert!(cache.has("test-model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_bit_rot() {
+        let dir = test_dir("verify");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"hello world");
+        cache.add("test-model", source, 11, None).await.unwrap();
+        assert!(cache.verify("test-model").await.unwrap());
+
+        // Corrupt the blob in place, bypassing the cache.
+        let path = cache.get("test-model").await.unwrap();
+        std::fs::write(&path, b"corrupted!!").unwrap();
+        assert!(!cache.verify("test-model").await.unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_unknown_reference_errors() {
+        let dir = test_dir("verify-missing");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        assert!(matches!(
+            cache.verify("nope").await,
+            Err(DmrletError::ModelNotFound(_))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scrub_clean_cache_is_a_no_op() {
+        let dir = test_dir("scrub-clean");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"healthy content");
+        cache.add("model", source, 15, None).await.unwrap();
+
+        let report = cache.scrub().await.unwrap();
+        assert!(report.repaired.is_empty());
+        assert!(report.orphans_removed.is_empty());
+        assert!(cache.has("model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scrub_drops_missing_blob() {
+        let dir = test_dir("scrub-missing");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"will go missing");
+        cache.add("model", source, 15, None).await.unwrap();
+
+        let path = cache.get("model").await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let report = cache.scrub().await.unwrap();
+        assert_eq!(report.repaired, vec!["model".to_string()]);
+        assert_eq!(report.bytes_reclaimed, 15);
+        assert!(!cache.has("model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scrub_detects_corruption() {
+        let dir = test_dir("scrub-corrupt");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"original content");
+        cache.add("model", source, 16, None).await.unwrap();
+
+        let path = cache.get("model").await.unwrap();
+        std::fs::write(&path, b"corrupted conten").unwrap();
+
+        let report = cache.scrub().await.unwrap();
+        assert_eq!(report.repaired, vec!["model".to_string()]);
+        assert!(!cache.has("model").await);
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scrub_reclaims_orphaned_blob() {
+        let dir = test_dir("scrub-orphan");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"kept content");
+        cache.add("model", source, 12, None).await.unwrap();
+
+        let orphan_dir = dir.join("store").join("sha256");
+        let orphan_path = orphan_dir.join("deadbeef-orphan");
+        std::fs::write(&orphan_path, b"nobody references me").unwrap();
+
+        let report = cache.scrub().await.unwrap();
+        assert_eq!(report.orphans_removed, vec![orphan_path.clone()]);
+        assert_eq!(report.orphan_bytes_reclaimed, 20);
+        assert!(!orphan_path.exists());
+        assert!(cache.has("model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expired_evicts_past_max_idle() {
+        let dir = test_dir("expiry-idle");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false)
+            .with_expiry_policy(ExpiryPolicy {
+                max_idle: Some(Duration::from_millis(1)),
+                max_ttl: None,
+                max_idle_overrides: HashMap::new(),
+            });
+
+        let source = write_source(&dir, "model.gguf", b"idle content");
+        cache.add("model", source, 12, None).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let expired = cache.sweep_expired().await.unwrap();
+        assert_eq!(expired, vec!["model".to_string()]);
+        assert!(!cache.has("model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expired_respects_per_reference_override() {
+        let dir = test_dir("expiry-override");
+        let mut overrides = HashMap::new();
+        overrides.insert("kept".to_string(), Duration::from_secs(3600));
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false)
+            .with_expiry_policy(ExpiryPolicy {
+                max_idle: Some(Duration::from_millis(1)),
+                max_ttl: None,
+                max_idle_overrides: overrides,
+            });
+
+        let kept = write_source(&dir, "kept.gguf", b"kept content");
+        let evicted = write_source(&dir, "evicted.gguf", b"evicted content");
+        cache.add("kept", kept, 12, None).await.unwrap();
+        cache.add("evicted", evicted, 15, None).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let expired = cache.sweep_expired().await.unwrap();
+        assert_eq!(expired, vec!["evicted".to_string()]);
+        assert!(cache.has("kept").await);
+        assert!(!cache.has("evicted").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expired_skips_pinned_model() {
+        let dir = test_dir("expiry-pin");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false)
+            .with_expiry_policy(ExpiryPolicy {
+                max_idle: Some(Duration::from_millis(1)),
+                max_ttl: None,
+                max_idle_overrides: HashMap::new(),
+            });
+
+        let source = write_source(&dir, "model.gguf", b"pinned content");
+        cache.add("model", source, 14, None).await.unwrap();
+        cache.acquire("model").await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let expired = cache.sweep_expired().await.unwrap();
+        assert!(expired.is_empty());
+        assert!(cache.has("model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sweep_expired_is_a_no_op_without_a_policy() {
+        let dir = test_dir("expiry-disabled");
+        let cache = ModelCache::ephemeral(dir.join("store"), 1024 * 1024, false);
+
+        let source = write_source(&dir, "model.gguf", b"never expires");
+        cache.add("model", source, 13, None).await.unwrap();
+
+        let expired = cache.sweep_expired().await.unwrap();
+        assert!(expired.is_empty());
+        assert!(cache.has("model").await);
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -2,11 +2,13 @@
 //!
 //! This crate provides model storage functionality:
 //! - Local model caching
-//! - OCI-based model handling (placeholder)
+//! - OCI registry pulls with content-addressed storage
 //! - LRU eviction
 
 pub mod cache;
+pub mod metadata;
 pub mod oci;
 
 pub use cache::ModelCache;
+pub use metadata::{CacheStore, MemoryCacheStore, SledCacheStore};
 pub use oci::OciStore;
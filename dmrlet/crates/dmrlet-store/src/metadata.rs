@@ -0,0 +1,159 @@
+//! Durable metadata storage for the model cache.
+//!
+//! [`ModelCache`](crate::cache::ModelCache) keeps its bookkeeping (which
+//! models are on disk, how large they are, when they were last used) in a
+//! [`CacheStore`] so a daemon restart can reconcile against it instead of
+//! forgetting every cached model and orphaning the GGUF files still sitting
+//! in `base_path`. The default implementation is an embedded sled database —
+//! the same class of store Garage uses for its block/object metadata tables
+//! — so no separate database service is required; a trivial in-memory store
+//! is also provided for tests.
+
+use crate::cache::CachedModel;
+use async_trait::async_trait;
+use dmrlet_core::{DmrletError, DmrletResult};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::sync::Mutex;
+
+/// Persistent store for [`CachedModel`] records, keyed by reference.
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Load every persisted record.
+    async fn load_all(&self) -> DmrletResult<Vec<CachedModel>>;
+    /// Persist (insert or update) a record.
+    async fn put(&self, model: &CachedModel) -> DmrletResult<()>;
+    /// Remove a record.
+    async fn delete(&self, reference: &str) -> DmrletResult<()>;
+}
+
+/// In-memory [`CacheStore`], used for tests and ephemeral runs with no
+/// durability requirement.
+#[derive(Default)]
+pub struct MemoryCacheStore {
+    models: Mutex<HashMap<String, CachedModel>>,
+}
+
+impl MemoryCacheStore {
+    /// Create an empty in-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CacheStore for MemoryCacheStore {
+    async fn load_all(&self) -> DmrletResult<Vec<CachedModel>> {
+        Ok(self.models.lock().await.values().cloned().collect())
+    }
+
+    async fn put(&self, model: &CachedModel) -> DmrletResult<()> {
+        self.models
+            .lock()
+            .await
+            .insert(model.reference.clone(), model.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, reference: &str) -> DmrletResult<()> {
+        self.models.lock().await.remove(reference);
+        Ok(())
+    }
+}
+
+/// Sled-backed [`CacheStore`], embedding metadata alongside the cached model
+/// files rather than requiring a separate database service.
+pub struct SledCacheStore {
+    db: sled::Db,
+}
+
+impl SledCacheStore {
+    /// Open (or create) the sled database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> DmrletResult<Self> {
+        let db = sled::open(path).map_err(map_sled)?;
+        Ok(Self { db })
+    }
+}
+
+#[async_trait]
+impl CacheStore for SledCacheStore {
+    async fn load_all(&self) -> DmrletResult<Vec<CachedModel>> {
+        self.db
+            .iter()
+            .values()
+            .map(|res| {
+                let bytes = res.map_err(map_sled)?;
+                serde_json::from_slice(&bytes).map_err(map_json)
+            })
+            .collect()
+    }
+
+    async fn put(&self, model: &CachedModel) -> DmrletResult<()> {
+        let bytes = serde_json::to_vec(model).map_err(map_json)?;
+        self.db
+            .insert(model.reference.as_bytes(), bytes)
+            .map_err(map_sled)?;
+        self.db.flush_async().await.map_err(map_sled)?;
+        Ok(())
+    }
+
+    async fn delete(&self, reference: &str) -> DmrletResult<()> {
+        self.db.remove(reference.as_bytes()).map_err(map_sled)?;
+        self.db.flush_async().await.map_err(map_sled)?;
+        Ok(())
+    }
+}
+
+/// Map a sled error into a `DmrletError`.
+fn map_sled(e: sled::Error) -> DmrletError {
+    DmrletError::Storage(format!("cache metadata store error: {}", e))
+}
+
+/// Map a (de)serialization error into a `DmrletError`.
+fn map_json(e: serde_json::Error) -> DmrletError {
+    DmrletError::Serialization(format!("cache metadata payload error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn test_model(reference: &str) -> CachedModel {
+        CachedModel {
+            reference: reference.to_string(),
+            path: format!("/tmp/{}.gguf", reference).into(),
+            size: 10,
+            last_accessed: SystemTime::now(),
+            downloaded_at: SystemTime::now(),
+            digest: "sha256:test".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_roundtrip() {
+        let store = MemoryCacheStore::new();
+        store.put(&test_model("m")).await.unwrap();
+
+        let loaded = store.load_all().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].reference, "m");
+
+        store.delete("m").await.unwrap();
+        assert!(store.load_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sled_store_roundtrip() {
+        let path = std::env::temp_dir().join(format!("dmrlet-cache-store-test-{}", std::process::id()));
+        let store = SledCacheStore::open(&path).unwrap();
+        store.put(&test_model("m")).await.unwrap();
+
+        let loaded = store.load_all().await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].reference, "m");
+
+        store.delete("m").await.unwrap();
+        assert!(store.load_all().await.unwrap().is_empty());
+    }
+}
@@ -1,96 +1,427 @@
-//! OCI-based model store (placeholder)
+//! OCI-based model store
 //!
-//! This module will provide OCI registry integration for pulling models.
-//! For now, it provides a basic interface that can be expanded later.
+//! This module provides OCI registry integration for pulling GGUF models.
+//! It speaks the Docker Registry v2 HTTP API: it performs the bearer-token
+//! handshake, fetches the image manifest, and streams the model's GGUF layer
+//! into a content-addressed store while verifying its SHA-256 digest. The registry
+//! host is resolved via DoH before each request, the same as health checks.
 
-use dmrlet_core::{DmrletError, DmrletResult};
-use std::path::PathBuf;
-use tracing::info;
+use dmrlet_core::{DmrletError, DmrletResult, Resolver};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info};
 
-/// OCI model store for pulling models from registries
+/// Default registry used when a reference omits an explicit host.
+const DEFAULT_REGISTRY: &str = "registry-1.docker.io";
+
+/// Media types accepted when fetching a manifest.
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.manifest.v1+json, \
+application/vnd.docker.distribution.manifest.v2+json";
+
+/// A parsed model reference such as `ai/llama3:8b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// Registry host (e.g. `registry-1.docker.io`).
+    pub registry: String,
+    /// Repository path (e.g. `ai/llama3`).
+    pub repository: String,
+    /// Tag (e.g. `8b`).
+    pub tag: String,
+}
+
+impl Reference {
+    /// Parse a reference string into registry/repository/tag components.
+    ///
+    /// A reference without a registry host defaults to Docker Hub, and a
+    /// reference without a tag defaults to `latest`.
+    pub fn parse(reference: &str) -> DmrletResult<Self> {
+        let (head, tag) = match reference.rsplit_once(':') {
+            // Guard against a port in the registry host being mistaken for a tag.
+            Some((h, t)) if !t.contains('/') => (h, t.to_string()),
+            _ => (reference, "latest".to_string()),
+        };
+
+        let (registry, repository) = match head.split_once('/') {
+            Some((host, rest)) if host.contains('.') || host.contains(':') => {
+                (host.to_string(), rest.to_string())
+            }
+            _ => (DEFAULT_REGISTRY.to_string(), head.to_string()),
+        };
+
+        if repository.is_empty() {
+            return Err(DmrletError::ModelNotFound(format!(
+                "Invalid model reference: {}",
+                reference
+            )));
+        }
+
+        Ok(Self {
+            registry,
+            repository,
+            tag,
+        })
+    }
+}
+
+/// Descriptor for a single layer or config blob in a manifest.
+#[derive(Debug, Clone, Deserialize)]
+struct Descriptor {
+    #[serde(rename = "mediaType")]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// Subset of an OCI/Docker image manifest that we care about.
+#[derive(Debug, Clone, Deserialize)]
+struct Manifest {
+    layers: Vec<Descriptor>,
+}
+
+/// Token response from a registry auth endpoint.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    #[serde(alias = "access_token")]
+    token: String,
+}
+
+/// Sidecar metadata mapping a reference to its resolved manifest digest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Metadata {
+    reference: String,
+    manifest_digest: String,
+    blob_digest: String,
+    path: PathBuf,
+}
+
+/// OCI model store for pulling models from registries.
 pub struct OciStore {
-    /// Base path for model storage
+    /// Base path for model storage.
     base_path: PathBuf,
+    /// HTTP client reused across requests.
+    client: reqwest::Client,
+    /// Resolver used to resolve the registry host before contacting it.
+    resolver: Arc<Resolver>,
 }
 
 impl OciStore {
-    /// Create a new OCI store
+    /// Create a new OCI store.
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self::with_resolver(base_path, Arc::new(Resolver::default()))
+    }
+
+    /// Create an OCI store with a shared resolver.
+    pub fn with_resolver(base_path: PathBuf, resolver: Arc<Resolver>) -> Self {
+        Self {
+            base_path,
+            client: reqwest::Client::new(),
+            resolver,
+        }
+    }
+
+    /// Resolve a registry host (optionally `host:port`) via DoH, preserving
+    /// any explicit port.
+    async fn resolve_registry(&self, registry: &str) -> String {
+        match registry.rsplit_once(':') {
+            Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => {
+                format!("{}:{}", self.resolver.resolve_one(host).await, port)
+            }
+            _ => self.resolver.resolve_one(registry).await,
+        }
     }
 
-    /// Pull a model from a registry
+    /// Pull a model from a registry.
     ///
-    /// This is a placeholder implementation. A full implementation would:
-    /// 1. Parse the model reference
-    /// 2. Authenticate with the registry
-    /// 3. Pull the model layers
-    /// 4. Extract and store the model file
+    /// Resolves the reference, performs the registry v2 token handshake,
+    /// fetches the manifest, and downloads the GGUF layer to a
+    /// content-addressed path while verifying its digest as it streams.
     pub async fn pull(&self, reference: &str) -> DmrletResult<PathBuf> {
-        info!(reference = reference, "Pulling model (placeholder)");
+        let parsed = Reference::parse(reference)?;
+        info!(
+            reference = reference,
+            registry = %parsed.registry,
+            repository = %parsed.repository,
+            tag = %parsed.tag,
+            "Pulling model"
+        );
+
+        let token = self.authenticate(&parsed).await?;
+        let (manifest, manifest_digest) = self.fetch_manifest(&parsed, &token).await?;
+
+        // Pick the GGUF layer; model manifests carry the weights as a single
+        // large blob layer.
+        let layer = manifest
+            .layers
+            .iter()
+            .find(|l| l.media_type.contains("gguf") || l.media_type.contains("octet-stream"))
+            .or_else(|| manifest.layers.first())
+            .ok_or_else(|| {
+                DmrletError::ModelNotFound(format!("No layers in manifest for {}", reference))
+            })?;
+
+        let path = self.blob_path(&layer.digest);
+        if !path.exists() {
+            self.download_blob(&parsed, &token, layer, &path).await?;
+        } else {
+            debug!(digest = %layer.digest, "Layer already present");
+        }
+
+        self.write_metadata(&Metadata {
+            reference: reference.to_string(),
+            manifest_digest,
+            blob_digest: layer.digest.clone(),
+            path: path.clone(),
+        })
+        .await?;
 
-        // For now, just return an error indicating this is not implemented
-        Err(DmrletError::ModelNotFound(format!(
-            "OCI pulling not yet implemented for: {}",
-            reference
-        )))
+        Ok(path)
     }
 
-    /// Check if a model exists in the store
-    pub fn exists(&self, reference: &str) -> bool {
-        let path = self.model_path(reference);
-        path.exists()
+    /// Perform the Docker Registry v2 token handshake.
+    ///
+    /// Probes `/v2/`, follows the `WWW-Authenticate` challenge to the auth
+    /// service, and returns a bearer token. An anonymous-access registry that
+    /// answers `200` to the probe yields an empty token.
+    async fn authenticate(&self, reference: &Reference) -> DmrletResult<String> {
+        let probe = format!(
+            "https://{}/v2/",
+            self.resolve_registry(&reference.registry).await
+        );
+        let resp = self
+            .client
+            .get(&probe)
+            .send()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Registry probe failed: {}", e)))?;
+
+        if resp.status().is_success() {
+            return Ok(String::new());
+        }
+
+        let challenge = resp
+            .headers()
+            .get("www-authenticate")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                DmrletError::Network("Registry did not issue an auth challenge".to_string())
+            })?;
+
+        let realm = parse_challenge(challenge, "realm")
+            .ok_or_else(|| DmrletError::Network("Auth challenge missing realm".to_string()))?;
+        let service = parse_challenge(challenge, "service").unwrap_or_default();
+        let scope = parse_challenge(challenge, "scope")
+            .unwrap_or_else(|| format!("repository:{}:pull", reference.repository));
+
+        let token: TokenResponse = self
+            .client
+            .get(&realm)
+            .query(&[("service", service.as_str()), ("scope", scope.as_str())])
+            .send()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Token request failed: {}", e)))?
+            .error_for_status()
+            .map_err(|e| DmrletError::Network(format!("Registry auth failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Invalid token response: {}", e)))?;
+
+        Ok(token.token)
     }
 
-    /// Get the local path for a model
-    pub fn model_path(&self, reference: &str) -> PathBuf {
-        // Use URL-safe base64 encoding to handle any characters in the reference
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+    /// Fetch a manifest and return it alongside its resolved digest.
+    async fn fetch_manifest(
+        &self,
+        reference: &Reference,
+        token: &str,
+    ) -> DmrletResult<(Manifest, String)> {
+        let url = format!(
+            "https://{}/v2/{}/manifests/{}",
+            self.resolve_registry(&reference.registry).await,
+            reference.repository,
+            reference.tag
+        );
+
+        let resp = self
+            .authed_get(&url, token)
+            .header("Accept", MANIFEST_ACCEPT)
+            .send()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Manifest fetch failed: {}", e)))?
+            .error_for_status()
+            .map_err(|e| DmrletError::ModelNotFound(format!("Manifest not found: {}", e)))?;
 
-        // Create a hash-based filename to avoid path issues
-        let mut hasher = DefaultHasher::new();
-        reference.hash(&mut hasher);
-        let hash = hasher.finish();
+        let digest = resp
+            .headers()
+            .get("docker-content-digest")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string())
+            .unwrap_or_default();
 
-        // Also include a sanitized version for readability
-        let safe_name: String = reference
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Manifest read failed: {}", e)))?;
+
+        // When the registry omits the digest header, derive it from the body.
+        let digest = if digest.is_empty() {
+            format!("sha256:{}", hex(&Sha256::digest(&bytes)))
+        } else {
+            digest
+        };
+
+        let manifest: Manifest = serde_json::from_slice(&bytes)?;
+        Ok((manifest, digest))
+    }
+
+    /// Stream a blob layer to `path`, verifying its SHA-256 as it arrives.
+    async fn download_blob(
+        &self,
+        reference: &Reference,
+        token: &str,
+        layer: &Descriptor,
+        path: &Path,
+    ) -> DmrletResult<()> {
+        let url = format!(
+            "https://{}/v2/{}/blobs/{}",
+            self.resolve_registry(&reference.registry).await,
+            reference.repository,
+            layer.digest
+        );
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut resp = self
+            .authed_get(&url, token)
+            .send()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Blob fetch failed: {}", e)))?
+            .error_for_status()
+            .map_err(|e| DmrletError::Network(format!("Blob fetch failed: {}", e)))?;
+
+        let tmp = path.with_extension("partial");
+        let mut file = tokio::fs::File::create(&tmp).await?;
+        let mut hasher = Sha256::new();
+
+        while let Some(chunk) = resp
+            .chunk()
+            .await
+            .map_err(|e| DmrletError::Network(format!("Blob stream error: {}", e)))?
+        {
+            hasher.update(&chunk);
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+
+        let actual = format!("sha256:{}", hex(&hasher.finalize()));
+        if actual != layer.digest {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(DmrletError::Storage(format!(
+                "Digest mismatch: expected {}, got {}",
+                layer.digest, actual
+            )));
+        }
+
+        tokio::fs::rename(&tmp, path).await?;
+        info!(digest = %layer.digest, bytes = layer.size, "Downloaded layer");
+        Ok(())
+    }
+
+    /// Build an authenticated GET request, attaching the bearer token if any.
+    fn authed_get(&self, url: &str, token: &str) -> reqwest::RequestBuilder {
+        let req = self.client.get(url);
+        if token.is_empty() {
+            req
+        } else {
+            req.bearer_auth(token)
+        }
+    }
+
+    /// Check if a model exists in the store by its resolved blob digest.
+    pub fn exists(&self, reference: &str) -> bool {
+        match self.read_metadata(reference) {
+            Some(meta) => self.blob_path(&meta.blob_digest).exists(),
+            None => false,
+        }
+    }
+
+    /// Content-addressed path for a blob digest (`sha256/<hex>`).
+    fn blob_path(&self, digest: &str) -> PathBuf {
+        let (algo, hex) = digest.split_once(':').unwrap_or(("sha256", digest));
+        self.base_path.join(algo).join(hex)
+    }
+
+    /// Path to the JSON metadata sidecar for a reference.
+    fn metadata_path(&self, reference: &str) -> PathBuf {
+        let safe: String = reference
             .chars()
             .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
             .collect();
+        self.base_path.join("refs").join(format!("{}.json", safe))
+    }
 
-        // Truncate to reasonable length and append hash for uniqueness
-        let truncated = if safe_name.len() > 50 {
-            &safe_name[..50]
-        } else {
-            &safe_name
-        };
+    /// Write the reference → digest metadata sidecar.
+    async fn write_metadata(&self, meta: &Metadata) -> DmrletResult<()> {
+        let path = self.metadata_path(&meta.reference);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let json = serde_json::to_vec_pretty(meta)?;
+        tokio::fs::write(&path, json).await?;
+        Ok(())
+    }
 
-        self.base_path.join(format!("{}_{:016x}.gguf", truncated, hash))
+    /// Read the metadata sidecar for a reference, if present.
+    fn read_metadata(&self, reference: &str) -> Option<Metadata> {
+        let path = self.metadata_path(reference);
+        let content = std::fs::read(path).ok()?;
+        serde_json::from_slice(&content).ok()
     }
 
-    /// List available models
+    /// List available models by their original references.
     pub async fn list(&self) -> DmrletResult<Vec<String>> {
-        // Note: This returns hash-based filenames since we use hash-based storage.
-        // A metadata file approach would be needed for proper reference tracking.
         let mut models = Vec::new();
+        let refs_dir = self.base_path.join("refs");
 
-        if !self.base_path.exists() {
+        if !refs_dir.exists() {
             return Ok(models);
         }
 
-        let mut entries = tokio::fs::read_dir(&self.base_path).await?;
+        let mut entries = tokio::fs::read_dir(&refs_dir).await?;
         while let Some(entry) = entries.next_entry().await? {
-            let path = entry.path();
-            if path.extension().map_or(false, |e| e == "gguf") {
-                if let Some(name) = path.file_stem() {
-                    models.push(name.to_string_lossy().to_string());
-                }
+            let content = tokio::fs::read(entry.path()).await?;
+            if let Ok(meta) = serde_json::from_slice::<Metadata>(&content) {
+                models.push(meta.reference);
             }
         }
 
+        models.sort();
         Ok(models)
     }
+
+    /// Get the local path for a model from its metadata sidecar.
+    pub fn model_path(&self, reference: &str) -> Option<PathBuf> {
+        self.read_metadata(reference).map(|m| m.path)
+    }
+}
+
+/// Extract a quoted parameter (e.g. `realm`) from a `WWW-Authenticate` header.
+fn parse_challenge(challenge: &str, key: &str) -> Option<String> {
+    let needle = format!("{}=\"", key);
+    let start = challenge.find(&needle)? + needle.len();
+    let rest = &challenge[start..];
+    let end = rest.find('"')?;
+    Some(rest[..end].to_string())
+}
+
+/// Hex-encode a byte slice.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
 #[cfg(test)]
@@ -98,17 +429,44 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_model_path() {
+    fn test_parse_reference() {
+        let r = Reference::parse("ai/llama3:8b").unwrap();
+        assert_eq!(r.registry, DEFAULT_REGISTRY);
+        assert_eq!(r.repository, "ai/llama3");
+        assert_eq!(r.tag, "8b");
+    }
+
+    #[test]
+    fn test_parse_reference_default_tag() {
+        let r = Reference::parse("ai/llama3").unwrap();
+        assert_eq!(r.tag, "latest");
+    }
+
+    #[test]
+    fn test_parse_reference_explicit_registry() {
+        let r = Reference::parse("ghcr.io/org/model:v1").unwrap();
+        assert_eq!(r.registry, "ghcr.io");
+        assert_eq!(r.repository, "org/model");
+        assert_eq!(r.tag, "v1");
+    }
+
+    #[test]
+    fn test_blob_path_content_addressed() {
         let store = OciStore::new(PathBuf::from("/var/lib/dmrlet/models"));
-        let path = store.model_path("ai/llama3:8b");
-        // Path should start with the base path and be a .gguf file
-        assert!(path.starts_with("/var/lib/dmrlet/models"));
-        assert!(path.extension().map_or(false, |e| e == "gguf"));
-        // Same reference should always produce the same path
-        let path2 = store.model_path("ai/llama3:8b");
-        assert_eq!(path, path2);
-        // Different references should produce different paths
-        let path3 = store.model_path("ai/llama3:70b");
-        assert_ne!(path, path3);
+        let path = store.blob_path("sha256:abcd");
+        assert!(path.ends_with("sha256/abcd"));
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        let header = "Bearer realm=\"https://auth.docker.io/token\",service=\"registry.docker.io\"";
+        assert_eq!(
+            parse_challenge(header, "realm").unwrap(),
+            "https://auth.docker.io/token"
+        );
+        assert_eq!(
+            parse_challenge(header, "service").unwrap(),
+            "registry.docker.io"
+        );
     }
 }